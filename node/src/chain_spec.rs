@@ -1,7 +1,8 @@
 use sp_core::{Pair, Public, sr25519};
 use idavoll_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	SystemConfig, IdavollConfig, WASM_BINARY, Signature, Balance,
+	pallet_idavoll::{OrgInfo, RuleKind},
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -53,12 +54,17 @@ pub fn development_config() -> Result<ChainSpec, String> {
 			vec![
 				authority_keys_from_seed("Alice"),
 			],
-			// Sudo account
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
+			// Meta organization members, the tokens of the organization are shared by them
+			vec![
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				get_account_id_from_seed::<sr25519::Public>("Bob"),
+				get_account_id_from_seed::<sr25519::Public>("Charlie"),
+			],
 			// Pre-funded accounts
 			vec![
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				get_account_id_from_seed::<sr25519::Public>("Bob"),
+				get_account_id_from_seed::<sr25519::Public>("Charlie"),
 				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 			],
@@ -93,14 +99,19 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				authority_keys_from_seed("Alice"),
 				authority_keys_from_seed("Bob"),
 			],
-			// Sudo account
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
+			// Meta organization members, the tokens of the organization are shared by them
+			vec![
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				get_account_id_from_seed::<sr25519::Public>("Bob"),
+				get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				get_account_id_from_seed::<sr25519::Public>("Dave"),
+			],
 			// Pre-funded accounts
 			vec![
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				// get_account_id_from_seed::<sr25519::Public>("Charlie"),
-				// get_account_id_from_seed::<sr25519::Public>("Dave"),
+				get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				get_account_id_from_seed::<sr25519::Public>("Dave"),
 				// get_account_id_from_seed::<sr25519::Public>("Eve"),
 				// get_account_id_from_seed::<sr25519::Public>("Ferdie"),
 				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
@@ -129,10 +140,14 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 fn testnet_genesis(
 	wasm_binary: &[u8],
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
-	root_key: AccountId,
+	root_members: Vec<AccountId>,
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
+	assert!(!root_members.is_empty(), "the meta organization needs at least one member");
+	// the tokens of the meta organization are shared equally by its members
+	let root_total = 1_000_000;
+	let root_share = root_total / root_members.len() as Balance;
 	GenesisConfig {
 		frame_system: Some(SystemConfig {
 			// Add Wasm runtime to storage.
@@ -149,9 +164,15 @@ fn testnet_genesis(
 		pallet_grandpa: Some(GrandpaConfig {
 			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
 		}),
-		pallet_sudo: Some(SudoConfig {
-			// Assign network admin rights.
-			key: root_key,
+		pallet_idavoll: Some(IdavollConfig {
+			// The meta organization governs the network, its proposals passed by the super
+			// majority can dispatch the calls with the Root origin.
+			organizations: vec![(
+				root_members[0].clone(),
+				root_total,
+				OrgInfo { rule: RuleKind::SuperMajority, ..OrgInfo::new() },
+				root_members[1..].iter().cloned().map(|k| (k, root_share)).collect(),
+			)],
 		}),
 	}
 }
@@ -28,10 +28,10 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use sp_std::{fmt::Debug};
+use sp_std::{fmt::Debug, prelude::Vec};
 use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch,
                     traits::{Get,Currency, ReservableCurrency,ExistenceRequirement::AllowDeath},
-                    Parameter,ensure};
+                    weights::Weight, Parameter,ensure};
 use frame_system::ensure_signed;
 use sp_runtime::{RuntimeDebug, ModuleId,
                  traits::{AtLeast32Bit,One,Zero,Member, AtLeast32BitUnsigned,
@@ -58,6 +58,12 @@ pub trait Trait: frame_system::Trait {
 
     /// The arithmetic type of asset identifier.
     type AssetId: Parameter + Member  + Ord + AtLeast32Bit + Default + Copy;
+
+    /// The maximum number of the scheduled unlocks released in a block.
+    type MaxUnlocksPerBlock: Get<u32>;
+    /// The maximum number of the blocks a scheduled unlock is delayed by when the blocks are full,
+    /// the unlock is scheduled at the last block over the `MaxUnlocksPerBlock` after it.
+    type MaxUnlockDelay: Get<u32>;
 }
 
 /// the balance of vault asset(IDV)
@@ -147,6 +153,8 @@ decl_storage! {
 		pub LockedBalance get(fn locked_balance): map hasher(blake2_128_concat) (T::AccountId,T::AccountId) => LocalBalance<T>;
 		/// The next token identifier.
 		NextAssetId get(fn next_asset_id): T::AssetId;
+		/// The locked tokens which will be unlocked at the block number.
+		pub UnlockSchedule get(fn unlock_schedule): map hasher(twox_64_concat) T::BlockNumber => Vec<(T::AssetId, T::AccountId, T::Balance)>;
 	}
 }
 
@@ -157,6 +165,11 @@ decl_module! {
 		fn deposit_event() = default;
         /// The idv-asset's module id, used for deriving its sovereign account ID.
 		const ModuleId: ModuleId = T::ModuleId::get();
+
+		/// Unlock the tokens which were scheduled to be unlocked at the block `n`.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::release_scheduled_locks(n)
+		}
 		/// Move some assets from one holder to another.
 		#[weight = 100_000]
 		pub fn transfer(origin,
@@ -292,6 +305,38 @@ impl<T: Trait> Module<T> {
         Self::deposit_event(RawEvent::UnLocked(id, who.clone(), amount));
         Ok(())
     }
+    /// Keep the `amount` locked tokens of `who` locked until the block `until`.
+    fn base_unlock_at(id: T::AssetId, who: &T::AccountId, amount: T::Balance, until: T::BlockNumber) -> dispatch::DispatchResult {
+        if until <= frame_system::Module::<T>::block_number() {
+            return Self::base_unlock(id, who, amount);
+        }
+        ensure!(Balances::<T>::get((id, who.clone())).frozen >= amount, Error::<T>::BalanceLow);
+        // the unlock is delayed to the next block with room when the block `until` is full
+        let mut until = until;
+        for _ in 0..T::MaxUnlockDelay::get() {
+            if UnlockSchedule::<T>::decode_len(until).unwrap_or(0) < T::MaxUnlocksPerBlock::get() as usize {
+                break;
+            }
+            until += One::one();
+        }
+        UnlockSchedule::<T>::mutate(until, |locks| locks.push((id, who.clone(), amount)));
+        Ok(())
+    }
+    /// The worst-case weight of `base_unlock_at`, the blocks are full for the `MaxUnlockDelay` blocks.
+    pub fn unlock_at_weight() -> Weight {
+        T::DbWeight::get().reads_writes((T::MaxUnlockDelay::get() as Weight).saturating_add(2), 2)
+    }
+    /// Unlock all the tokens scheduled at the block `n`, they are up to `MaxUnlocksPerBlock`
+    /// unless the unlocks were delayed by the `MaxUnlockDelay` blocks.
+    fn release_scheduled_locks(n: T::BlockNumber) -> Weight {
+        let locks = UnlockSchedule::<T>::take(n);
+        let count = locks.len() as Weight;
+        locks.into_iter().for_each(|(id, who, amount)| {
+            // the tokens were kept locked, so unlocking them never fails
+            let _ = Self::base_unlock(id, &who, amount);
+        });
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
 
 
     pub fn vault_balance_of(oid: T::AccountId) -> Result<LocalBalance<T>, dispatch::DispatchError> {
@@ -360,7 +405,8 @@ impl<T: Trait> Module<T> {
 mod test {
     use super::*;
 
-    use frame_support::{impl_outer_origin, assert_ok, assert_noop, parameter_types, weights::Weight};
+    use frame_support::{impl_outer_origin, assert_ok, assert_noop, parameter_types, weights::Weight, traits::OnInitialize};
+    use crate::token::BaseToken;
     use sp_core::H256;
     use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
 
@@ -385,6 +431,8 @@ mod test {
 		pub const MaximumBlockLength: u32 = 2 * 1024;
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
 		pub const IdvAssetModuleId: ModuleId = ModuleId(*b"py/asset");
+		pub const MaxUnlocksPerBlock: u32 = 2;
+		pub const MaxUnlockDelay: u32 = 1;
 	}
     impl frame_system::Trait for Test {
         type BaseCallFilter = ();
@@ -433,6 +481,8 @@ mod test {
         type AssetId = u32;
         type Currency = IdvBalances;
         type ModuleId = IdvAssetModuleId;
+        type MaxUnlocksPerBlock = MaxUnlocksPerBlock;
+        type MaxUnlockDelay = MaxUnlockDelay;
     }
     type IdavollAsset = Module<Test>;
 
@@ -522,6 +572,37 @@ mod test {
         });
     }
 
+    #[test]
+    fn scheduled_unlock_should_work() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(IdavollAsset::create_token(1, 100),0);
+            assert_ok!(IdavollAsset::base_lock(0, &1, 60));
+            System::set_block_number(2);
+            assert_ok!(IdavollAsset::unlock_at(0, &1, 20, 2));
+            assert_eq!(IdavollAsset::free_balance(0, &1), 60);
+            assert_ok!(IdavollAsset::unlock_at(0, &1, 30, 5));
+            assert_ok!(IdavollAsset::unlock_at(0, &1, 5, 5));
+            assert_ok!(IdavollAsset::unlock_at(0, &1, 5, 5));
+            assert_noop!(IdavollAsset::unlock_at(0, &1, 50, 6), Error::<Test>::BalanceLow);
+            assert_eq!(IdavollAsset::free_balance(0, &1), 60);
+            // the block 5 is full, the third unlock was delayed to the block 6
+            assert_eq!(IdavollAsset::unlock_schedule(6), vec![(0, 1, 5)]);
+            // the unlock is delayed by one block at most, so the full block 6 takes it
+            assert_ok!(IdavollAsset::base_lock(0, &1, 2));
+            assert_ok!(IdavollAsset::unlock_at(0, &1, 1, 6));
+            assert_ok!(IdavollAsset::unlock_at(0, &1, 1, 5));
+            assert_eq!(IdavollAsset::unlock_schedule(6), vec![(0, 1, 5), (0, 1, 1), (0, 1, 1)]);
+
+            IdavollAsset::on_initialize(4);
+            assert_eq!(IdavollAsset::free_balance(0, &1), 58);
+            IdavollAsset::on_initialize(5);
+            assert_eq!(IdavollAsset::free_balance(0, &1), 93);
+            assert_eq!(IdavollAsset::unlock_schedule(5), vec![]);
+            IdavollAsset::on_initialize(6);
+            assert_eq!(IdavollAsset::free_balance(0, &1), 100);
+        });
+    }
+
     #[test]
     fn vault_transfer_and_balance_should_work() {
         new_test_ext().execute_with(|| {
@@ -20,6 +20,7 @@ use sp_runtime::{
     traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize,Saturating},
     DispatchResult,
 };
+use frame_support::weights::Weight;
 use sp_std::{
     cmp::{Eq, PartialEq},
     fmt::Debug,
@@ -35,6 +36,9 @@ pub trait BaseToken<AccountId> {
     /// The type used to identify currencies
     type AssetId: FullCodec + Eq + PartialEq + Copy + Debug + Default;
 
+    /// The block number type used to schedule the unlocking.
+    type BlockNumber: FullCodec + Copy + Debug;
+
     /// The balance of an account.
     type Balance: AtLeast32BitUnsigned
     + FullCodec
@@ -74,11 +78,19 @@ pub trait BaseToken<AccountId> {
     /// Unlock `value` from locked balance to free balance. This function cannot fail.
     /// If the locked balance of `who` is less than `value`, then the remaining amount will be returned.
     fn unlock(aid: Self::AssetId, who: &AccountId, value: Self::Balance) -> DispatchResult;
+
+    /// Keep `value` locked balance locked until the block `until`, it will be unlocked automatically
+    /// at the block. Return `Err` if the locked balance of `who` is lower than `value`.
+    fn unlock_at(aid: Self::AssetId, who: &AccountId, value: Self::Balance, until: Self::BlockNumber) -> DispatchResult;
+
+    /// The worst-case weight of `unlock_at`.
+    fn unlock_at_weight() -> Weight;
 }
 
 
 impl<T: Trait> BaseToken<T::AccountId> for Module<T> {
     type AssetId = T::AssetId;
+    type BlockNumber = T::BlockNumber;
     type Balance = T::Balance;
 
 
@@ -129,4 +141,14 @@ impl<T: Trait> BaseToken<T::AccountId> for Module<T> {
     fn unlock(aid: Self::AssetId, who: &T::AccountId, value: Self::Balance) -> DispatchResult{
         Self::base_unlock(aid,who,value)
     }
+
+    /// Keep `value` locked balance locked until the block `until`, it will be unlocked automatically
+    /// at the block. Return `Err` if the locked balance of `who` is lower than `value`.
+    fn unlock_at(aid: Self::AssetId, who: &T::AccountId, value: Self::Balance, until: Self::BlockNumber) -> DispatchResult {
+        Self::base_unlock_at(aid,who,value,until)
+    }
+
+    fn unlock_at_weight() -> Weight {
+        Self::unlock_at_weight()
+    }
 }
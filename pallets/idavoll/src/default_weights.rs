@@ -47,4 +47,90 @@ impl crate::WeightInfo for () {
             .saturating_add(DbWeight::get().reads((100_u64).saturating_mul(10_u64)))
             .saturating_add(DbWeight::get().writes((500_u64).saturating_mul(10_u64)))
     }
-}
+    fn close_expired_proposals(n: u32) -> Weight {
+        (10_000_000_u64)
+            .saturating_add((150_000_000_u64).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(1_u64))
+            .saturating_add(DbWeight::get().reads((100_u64).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes((100_u64).saturating_mul(n as Weight)))
+    }
+    fn close_proposal() -> Weight {
+        (200_000_000_u64)
+            .saturating_add(DbWeight::get().reads((100_u64).saturating_mul(10_u64)))
+            .saturating_add(DbWeight::get().writes((100_u64).saturating_mul(10_u64)))
+    }
+    fn cancel_proposal() -> Weight {
+        (150_000_000_u64)
+            .saturating_add(DbWeight::get().reads((100_u64).saturating_mul(10_u64)))
+            .saturating_add(DbWeight::get().writes((100_u64).saturating_mul(10_u64)))
+    }
+    fn leave_organization() -> Weight {
+        (200_000_000_u64)
+            .saturating_add(DbWeight::get().reads((100_u64).saturating_mul(10_u64)))
+            .saturating_add(DbWeight::get().writes((100_u64).saturating_mul(10_u64)))
+    }
+    fn remove_member() -> Weight {
+        (200_000_000_u64)
+            .saturating_add(DbWeight::get().reads((100_u64).saturating_mul(10_u64)))
+            .saturating_add(DbWeight::get().writes((100_u64).saturating_mul(10_u64)))
+    }
+    fn add_member() -> Weight {
+        (100_000_000_u64)
+            .saturating_add(DbWeight::get().reads(1_u64))
+            .saturating_add(DbWeight::get().writes(1_u64))
+    }
+    fn update_org_param() -> Weight {
+        (50_000_000_u64)
+            .saturating_add(DbWeight::get().reads(1_u64))
+            .saturating_add(DbWeight::get().writes(1_u64))
+    }
+    fn remove_vote() -> Weight {
+        (80_000_000_u64)
+            .saturating_add(DbWeight::get().reads(3_u64))
+            .saturating_add(DbWeight::get().writes(2_u64))
+    }
+    fn delegate() -> Weight {
+        (80_000_000_u64)
+            .saturating_add(DbWeight::get().reads(5_u64))
+            .saturating_add(DbWeight::get().writes(4_u64))
+    }
+    fn undelegate() -> Weight {
+        (60_000_000_u64)
+            .saturating_add(DbWeight::get().reads(3_u64))
+            .saturating_add(DbWeight::get().writes(3_u64))
+    }
+    fn set_enactment_delay() -> Weight {
+        (50_000_000_u64)
+            .saturating_add(DbWeight::get().reads(1_u64))
+            .saturating_add(DbWeight::get().writes(1_u64))
+    }
+    fn veto_proposal() -> Weight {
+        (60_000_000_u64)
+            .saturating_add(DbWeight::get().reads(1_u64))
+            .saturating_add(DbWeight::get().writes(2_u64))
+    }
+    fn enact_queued_proposals(n: u32) -> Weight {
+        (10_000_000_u64)
+            .saturating_add((100_000_000_u64).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(1_u64))
+            .saturating_add(DbWeight::get().reads((2_u64).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(1_u64))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(n as Weight)))
+    }
+    fn set_guardian() -> Weight {
+        (50_000_000_u64)
+            .saturating_add(DbWeight::get().reads(1_u64))
+            .saturating_add(DbWeight::get().writes(1_u64))
+    }
+    fn set_call_whitelist() -> Weight {
+        (50_000_000_u64)
+            .saturating_add(DbWeight::get().reads(1_u64))
+            .saturating_add(DbWeight::get().writes(1_u64))
+    }
+    fn dispatch_as_root() -> Weight {
+        (10_000_000_u64)
+    }
+    fn dispatch_as_account() -> Weight {
+        (10_000_000_u64)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,155 @@
+/*
+ * Copyright 2021 Idavoll Network
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use frame_support::{ensure, dispatch::{DispatchError, DispatchResult}, traits::Get, weights::Weight, StorageDoubleMap};
+use sp_runtime::traits::{Saturating, Zero};
+use crate::{Error, Module, RawEvent, Trait, WeightInfo, BalanceOf, CallCategory, Conviction, Delegation, VotingMode,
+            Delegations, Delegators, ProposalOf, ProposalDetailOf};
+use idavoll_asset::token::BaseToken;
+use codec::Encode;
+use sp_std::prelude::Vec;
+
+impl<T: Trait> Module<T> {
+    /// The member `who` delegates the voting power of `value` tokens to the member `target`,
+    /// the tokens are locked until undelegated. The delegation replaces the previous delegation
+    /// of the `category`, and the delegate can take up to `MaxDelegatorsPerDelegate` delegators.
+    pub fn on_delegate(id: u32, who: T::AccountId, target: T::AccountId,
+                       category: Option<CallCategory>, value: BalanceOf<T>) -> DispatchResult {
+        let oid = Self::counter_2_orgid(id);
+        let org = Self::get_orginfo_by_id(oid.clone())?;
+        let aid = org.get_asset_id();
+        ensure!(who != target,Error::<T>::DelegateToSelf);
+        ensure!(Self::is_member(oid.clone(),&who),Error::<T>::NotMemberInOrg);
+        ensure!(Self::is_member(oid.clone(),&target),Error::<T>::NotMemberInOrg);
+        let previous = Delegations::<T>::get(&oid,(who.clone(),category));
+        let replaced = previous.as_ref().map_or(false,|d| d.target == target) as usize;
+        let delegators = Delegators::<T>::decode_len(&oid,&target).unwrap_or(0);
+        ensure!(delegators.saturating_sub(replaced) < T::MaxDelegatorsPerDelegate::get() as usize,
+            Error::<T>::TooManyDelegators);
+        // every member takes one vote in the membership mode, so no tokens are delegated
+        let value = if org.mode == VotingMode::Membership { Zero::zero() } else { value };
+        let previous = previous.map(|d| d.balance).unwrap_or_default();
+        let free = T::TokenHandler::free_balance_of(aid, &who);
+        ensure!(free.saturating_add(previous) >= value,Error::<T>::TokenBalanceLow);
+
+        Self::base_undelegate(oid.clone(),aid,who.clone(),category)?;
+        if !value.is_zero() {
+            T::TokenHandler::lock(aid, &who, value)?;
+        }
+        Delegations::<T>::insert(&oid,(who.clone(),category),Delegation{
+            target: target.clone(),
+            balance: value,
+        });
+        Delegators::<T>::append(&oid,&target,(who.clone(),category));
+        Self::deposit_event(RawEvent::Delegated(oid,who,target,category,value));
+        Ok(())
+    }
+    /// Remove the delegation of the `category` and unlock the delegated tokens.
+    pub fn on_undelegate(id: u32, who: T::AccountId, category: Option<CallCategory>) -> DispatchResult {
+        let oid = Self::counter_2_orgid(id);
+        let aid = Self::get_orginfo_by_id(oid.clone())?.get_asset_id();
+        ensure!(Delegations::<T>::contains_key(&oid,(who.clone(),category)),Error::<T>::DelegationNotFound);
+        Self::base_undelegate(oid.clone(),aid,who.clone(),category)?;
+        Self::deposit_event(RawEvent::Undelegated(oid,who,category));
+        Ok(())
+    }
+    /// Remove the delegations of the member `who` and the delegations to it, the delegated
+    /// tokens are unlocked. Every call category is checked for the delegations of the member.
+    /// Returns the weight of the removed delegations.
+    pub fn base_undelegate_all(oid: T::AccountId, aid: T::TokenId, who: T::AccountId) -> Result<Weight, DispatchError> {
+        let mut removed = 0u32;
+        let categories = sp_std::iter::once(None).chain((0..=CallCategory::max_value()).map(Some));
+        for category in categories {
+            if Delegations::<T>::contains_key(&oid,(who.clone(),category)) {
+                Self::base_undelegate(oid.clone(),aid,who.clone(),category)?;
+                Self::deposit_event(RawEvent::Undelegated(oid.clone(),who.clone(),category));
+                removed = removed.saturating_add(1);
+            }
+        }
+        for (delegator,category) in Delegators::<T>::get(&oid,&who) {
+            Self::base_undelegate(oid.clone(),aid,delegator.clone(),category)?;
+            Self::deposit_event(RawEvent::Undelegated(oid.clone(),delegator,category));
+            removed = removed.saturating_add(1);
+        }
+        Ok(Self::undelegate_all_weight_of(removed))
+    }
+    /// The worst-case weight of `base_undelegate_all`, the member delegates in every call category
+    /// and the member takes `MaxDelegatorsPerDelegate` delegators.
+    pub fn undelegate_all_weight() -> Weight {
+        let delegations = (CallCategory::max_value() as u32).saturating_add(1)
+            .saturating_add(T::MaxDelegatorsPerDelegate::get());
+        Self::undelegate_all_weight_of(delegations)
+    }
+    /// The weight of `base_undelegate_all` which removed the `removed` delegations.
+    pub fn undelegate_all_weight_of(removed: u32) -> Weight {
+        let categories = (CallCategory::max_value() as Weight).saturating_add(1);
+        T::DbWeight::get().reads(categories.saturating_add(1))
+            .saturating_add(T::WeightInfo::undelegate().saturating_mul(removed as Weight))
+    }
+    fn base_undelegate(oid: T::AccountId, aid: T::TokenId, who: T::AccountId,
+                       category: Option<CallCategory>) -> DispatchResult {
+        if let Some(delegation) = Delegations::<T>::take(&oid,(who.clone(),category)) {
+            Delegators::<T>::mutate_exists(&oid,&delegation.target,|maybe_delegators| {
+                if let Some(delegators) = maybe_delegators {
+                    delegators.retain(|x| *x != (who.clone(),category));
+                    if delegators.is_empty() {
+                        *maybe_delegators = None;
+                    }
+                }
+            });
+            if !delegation.balance.is_zero() {
+                T::TokenHandler::unlock(aid, &who, delegation.balance)?;
+            }
+        }
+        Ok(())
+    }
+    /// The call category of the proposal, it's `None` if the calls are in different categories.
+    pub fn proposal_category(proposal: &ProposalOf<T>) -> Option<CallCategory> {
+        let categories: Vec<Option<CallCategory>> = Self::decode_calls(&proposal.call).ok()?
+            .iter()
+            .map(|call| call.encode().first().copied())
+            .collect();
+        let first = *categories.first()?;
+        if categories.iter().all(|c| *c == first) { first } else { None }
+    }
+    /// The details of the proposal with the delegated votes, the delegators take the stance of
+    /// their delegates. The delegators who voted directly or left the organization are skipped,
+    /// and the delegation of the call category of the proposal takes precedence over the
+    /// delegation of all proposals.
+    pub fn detail_with_delegations(proposal: &ProposalOf<T>) -> ProposalDetailOf<T> {
+        let mut detail = proposal.detail.clone();
+        let category = Self::proposal_category(proposal);
+        for (delegate,(_,stance,_)) in proposal.detail.votes.iter() {
+            for (delegator,scope) in Delegators::<T>::get(&proposal.org,delegate) {
+                if detail.votes.contains_key(&delegator) || !Self::is_member(proposal.org.clone(),&delegator) {
+                    continue;
+                }
+                let applied = match scope {
+                    Some(_) => scope == category,
+                    None => category.map_or(true,|c|
+                        !Delegations::<T>::contains_key(&proposal.org,(delegator.clone(),Some(c)))),
+                };
+                if !applied {
+                    continue;
+                }
+                if let Some(delegation) = Delegations::<T>::get(&proposal.org,(delegator.clone(),scope)) {
+                    detail.vote(delegator,delegation.balance,*stance,Conviction::None);
+                }
+            }
+        }
+        detail
+    }
+}
@@ -19,22 +19,23 @@
 
 
 use frame_support::{
-	codec::{Decode},
-	decl_module, decl_storage, decl_event, decl_error,
+	codec::{Decode, Encode},
+	decl_module, decl_storage, decl_event, decl_error, ensure,
 	dispatch::{
-		self,Dispatchable, Parameter, PostDispatchInfo,
+		self,Dispatchable, Parameter, PostDispatchInfo, DispatchResultWithPostInfo, IsSubType,
 	},
-	traits::{Get},
+	traits::{Get, EnsureOrigin},
 	weights::{GetDispatchInfo, Weight},
+	storage::{with_transaction, TransactionOutcome},
 };
 use frame_system::ensure_signed;
 use sp_runtime::{
-	ModuleId,
+	ModuleId, RuntimeDebug,
 	traits::{StaticLookup, AccountIdConversion,
-			 Saturating,AtLeast32BitUnsigned,AtLeast32Bit,
+			 Saturating,AtLeast32BitUnsigned,AtLeast32Bit,Zero,
 			 Member,MaybeSerializeDeserialize,
 	}};
-use sp_std::{boxed::Box, prelude::Vec};
+use sp_std::{prelude::Vec, boxed::Box};
 
 #[cfg(test)]
 mod mock;
@@ -45,11 +46,16 @@ mod tests;
 mod organization;
 mod rules;
 mod voting;
+mod delegation;
 mod default_weights;
+mod migration;
+mod origin;
 
-pub use organization::{OrgInfo, Proposal,ProposalDetail,ProposalDetailOf};
+pub use organization::{OrgInfo, Proposal,ProposalDetail,ProposalDetailOf,Vote,Conviction,VotingMode,AdmissionPolicy,MemberRecord,Delegation,CallFilter};
 use idavoll_asset::{token::BaseToken,finance::BaseFinance};
 use rules::{OrgRuleParam};
+pub use rules::{BaseRule, DefaultRule, RuleKind, ApprovalMode};
+pub use origin::{RawOrigin, Origin, Tally, EnsureOrg, EnsureOrgThreshold, EnsureMetaOrg};
 
 
 pub trait WeightInfo {
@@ -58,16 +64,41 @@ pub trait WeightInfo {
 	fn create_proposal() -> Weight;
 	fn vote_proposal() -> Weight;
 	fn add_member_and_assign_token() -> Weight;
+	fn close_expired_proposals(n: u32) -> Weight;
+	fn close_proposal() -> Weight;
+	fn cancel_proposal() -> Weight;
+	fn leave_organization() -> Weight;
+	fn remove_member() -> Weight;
+	fn add_member() -> Weight;
+	fn update_org_param() -> Weight;
+	fn remove_vote() -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn set_enactment_delay() -> Weight;
+	fn veto_proposal() -> Weight;
+	fn set_guardian() -> Weight;
+	fn set_call_whitelist() -> Weight;
+	fn enact_queued_proposals(n: u32) -> Weight;
+	fn dispatch_as_root() -> Weight;
+	fn dispatch_as_account() -> Weight;
 }
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
+	/// The outer origin type, the calls of the passed proposals are dispatched with the
+	/// organization origin.
+	type Origin: From<Origin<Self>>
+	+ From<<Self as frame_system::Trait>::Origin>
+	+ Into<Result<Origin<Self>, <Self as Trait>::Origin>>;
+
 	/// The outer call dispatch type.
 	type Call: Parameter
-	+ Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo>
+	+ Dispatchable<Origin=<Self as Trait>::Origin, PostInfo=PostDispatchInfo>
 	+ From<frame_system::Call<Self>>
+	+ From<Call<Self>>
+	+ IsSubType<Call<Self>>
 	+ GetDispatchInfo;
 
 	/// The idavoll pallet's module id, used for deriving the organization id.
@@ -77,16 +108,58 @@ pub trait Trait: frame_system::Trait {
 	type TokenHandler: BaseToken<
 		Self::AccountId,
 		AssetId = Self::TokenId,
+		BlockNumber = Self::BlockNumber,
 		Balance = Self::Balance,
 	>;
 
 	type Balance: Member + Parameter + AtLeast32BitUnsigned + MaybeSerializeDeserialize + Default + Copy;
 	/// the vaults of all organizations
 	type Finance: BaseFinance<Self::AccountId,Self::Balance>;
-	type TokenId: Parameter + AtLeast32Bit + Default + Copy;
+	type TokenId: Parameter + AtLeast32Bit + MaybeSerializeDeserialize + Default + Copy;
+
+	/// The rule engine decides whether the proposal was passed, expired or can be closed,
+	/// the `Data` is the rule kind and the total tokens of the organization.
+	type RuleHandler: BaseRule<
+		AccountId = Self::AccountId,
+		BlockNumber = Self::BlockNumber,
+		Params = ProposalDetailOf<Self>,
+		Data = (RuleKind, BalanceOf<Self>),
+	>;
 
 	/// the staking balance of local asset by user create proposal.
 	type InherentStakeProposal: Get<BalanceOf<Self>>;
+	/// the maximum number of the expired proposals will be closed in a block.
+	type MaxExpiredPerBlock: Get<u32>;
+	/// The length of a vote locking period, the tokens of the conviction votes are kept locked
+	/// for the periods of the conviction after the proposal ended.
+	type VoteLockingPeriod: Get<Self::BlockNumber>;
+	/// the maximum number of the queued proposals will be enacted in a block.
+	type MaxEnactmentsPerBlock: Get<u32>;
+	/// the maximum dispatch weight of the queued proposals will be enacted in a block, it's counted
+	/// by the weight of the calls stored in the proposals.
+	type MaxEnactmentWeight: Get<Weight>;
+	/// the maximum length of the title of the proposal.
+	type MaxTitleLength: Get<u32>;
+	/// the maximum length of the content hash or CID of the proposal.
+	type MaxContentLength: Get<u32>;
+	/// the maximum number of the calls in a proposal.
+	type MaxProposalCalls: Get<u32>;
+	/// the maximum number of the delegators of a delegate in the organization, the votes are
+	/// counted with the delegators of every voter.
+	type MaxDelegatorsPerDelegate: Get<u32>;
+	/// the maximum dispatch weight of the calls in a proposal.
+	type MaxProposalWeight: Get<Weight>;
+	/// the maximum number of the members of an organization, the total votes of the quadratic
+	/// voting are counted by the balances of all members.
+	type MaxMembers: Get<u32>;
+	/// The origin can veto the passed proposals during the enactment delay.
+	type VetoOrigin: EnsureOrigin<<Self as frame_system::Trait>::Origin>;
+	/// The number of the organization becomes the meta organization on the runtime upgrade, it's
+	/// used by the chains which have no meta organization created at genesis.
+	type MetaOrgNumber: Get<Option<OrgCount>>;
+	/// The origin can dispatch the calls with the Root origin, such as the passed proposals of
+	/// the meta organization created at genesis.
+	type RootOrigin: EnsureOrigin<<Self as frame_system::Trait>::Origin>;
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -98,7 +171,9 @@ pub type OrgInfoOf<T> = OrgInfo<
 	BalanceOf<T>,
 	<T as Trait>::TokenId,
 >;
-pub type ProposalIdOf<T> = <T as frame_system::Trait>::Hash;
+pub type ProposalIndex = u32;
+/// The proposal id is the organization number and the index of the proposal in the organization
+pub type ProposalId = (OrgCount, ProposalIndex);
 
 pub type ProposalOf<T> = Proposal<
 	Vec<u8>,
@@ -107,13 +182,86 @@ pub type ProposalOf<T> = Proposal<
 	<T as frame_system::Trait>::BlockNumber,
 >;
 pub type OrgRuleParamOf<T> = OrgRuleParam<BalanceOf<T>>;
+/// The category of the calls, it is the index of the pallet of the call in the runtime,
+/// that is the first byte of the encoded call.
+pub type CallCategory = u8;
+pub type DelegationOf<T> = Delegation<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+pub type MemberRecordOf<T> = MemberRecord<<T as frame_system::Trait>::BlockNumber>;
+
+/// The storage layouts of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	/// The first release, the members were stored in the `OrgInfo` and the proposals were
+	/// keyed by the hash of the proposal
+	V1,
+	/// The members were stored in the `Members`
+	V2,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
+}
 
 // The pallet's runtime storage items.
 decl_storage! {
 	trait Store for Module<T: Trait> as IdavollModule {
 		pub OrgCounter get(fn counter): OrgCount = 0;
 		pub OrgInfos get(fn org_infos): map hasher(blake2_128_concat) T::AccountId => Option<OrgInfoOf<T>>;
-        pub Proposals get(fn proposals): map hasher(blake2_128_concat) ProposalIdOf<T> => Option<ProposalOf<T>>;
+		/// The members of the organization, keyed by the organization id and the account.
+		pub Members get(fn members): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<MemberRecordOf<T>>;
+		/// The count of the members in the organization.
+		pub MemberCount get(fn member_count): map hasher(blake2_128_concat) T::AccountId => u32;
+        pub Proposals get(fn proposals): map hasher(blake2_128_concat) ProposalId => Option<ProposalOf<T>>;
+		/// The index of the next proposal in the organization, keyed by the organization number.
+		pub ProposalCounter get(fn proposal_counter): map hasher(twox_64_concat) OrgCount => ProposalIndex;
+		/// The active proposal ids of the organization, keyed by the organization id.
+		pub OrgProposals get(fn org_proposals): map hasher(blake2_128_concat) T::AccountId => Vec<ProposalId>;
+		/// The count of the active proposals in all organizations.
+		pub ProposalCount get(fn count_of_proposals): u32;
+		/// The proposals indexed by the end block number(`end_dt`), they will be closed after expired.
+		pub ExpiringProposals get(fn expiring_proposals): map hasher(twox_64_concat) T::BlockNumber => Vec<ProposalId>;
+		/// The delegations of the members, keyed by the organization id and the (delegator, call category).
+		/// The delegation without the call category applies to all proposals.
+		pub Delegations get(fn delegations): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) (T::AccountId, Option<CallCategory>) => Option<DelegationOf<T>>;
+		/// The (delegator, call category) delegated to the delegate, keyed by the organization id and the delegate.
+		pub Delegators get(fn delegators): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Vec<(T::AccountId, Option<CallCategory>)>;
+		/// The blocks between passing and executing the proposals of the organization.
+		pub EnactmentDelay get(fn enactment_delay): map hasher(blake2_128_concat) T::AccountId => T::BlockNumber;
+		/// The passed proposals waiting for the execution and the block number they will be executed.
+		pub QueuedProposals get(fn queued_proposals): map hasher(blake2_128_concat) ProposalId => Option<(T::BlockNumber, ProposalOf<T>)>;
+		/// The id of the meta organization created at genesis or set by the `MetaOrgNumber` on
+		/// the runtime upgrade.
+		pub MetaOrganization get(fn meta_organization): Option<T::AccountId>;
+		/// The queued proposals indexed by the block number they will be executed.
+		pub EnactmentQueue get(fn enactment_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<ProposalId>;
+		/// The calls allowed in the proposals of the organization, all calls are allowed without it.
+		pub CallWhitelist get(fn call_whitelist): map hasher(blake2_128_concat) T::AccountId => Option<Vec<CallFilter>>;
+		/// The storage layout of the pallet, it is used by the storage migrations.
+		StorageVersion get(fn storage_version) build(|_: &GenesisConfig<T>| Releases::V2): Releases;
+	}
+	add_extra_genesis {
+		/// The organizations created at genesis \[creator, total, details, members\], the creator
+		/// holds the `total` tokens and transfers the given tokens to every member. The first one
+		/// is the meta organization of the network.
+		config(organizations): Vec<(T::AccountId, T::Balance, OrgInfoOf<T>, Vec<(T::AccountId, T::Balance)>)>;
+		build(|config: &GenesisConfig<T>| {
+			for (creator, total, info, members) in config.organizations.iter() {
+				let oid = Module::<T>::counter_2_orgid(Module::<T>::counter());
+				Module::<T>::on_create_organization(creator.clone(), *total, info.clone(),
+					members.iter().map(|(who, _)| who.clone()).collect())
+					.expect("the organizations in genesis are valid; qed");
+				let asset_id = OrgInfos::<T>::get(&oid).expect("the organization was created; qed").get_asset_id();
+				for (who, value) in members.iter() {
+					T::TokenHandler::transfer(asset_id, creator, who, *value)
+						.expect("the creator holds the tokens of the members; qed");
+				}
+				if MetaOrganization::<T>::get().is_none() {
+					MetaOrganization::<T>::put(oid);
+				}
+			}
+		});
 	}
 }
 
@@ -123,19 +271,49 @@ decl_event!(
 	pub enum Event<T>
 	where
 	AccountId = <T as frame_system::Trait>::AccountId,
-	ProposalId = ProposalIdOf<T>,
 	OrgInfo = OrgInfoOf<T>,
+	OrgRuleParam = OrgRuleParamOf<T>,
+	Balance = BalanceOf<T>,
+	BlockNumber = <T as frame_system::Trait>::BlockNumber,
 	{
 		/// An organization was created with the following parameters. \[organization_id, organization_number, details\]
         OrganizationCreated(AccountId, u32, OrgInfo),
-		/// A proposal has been finalized with the following result. \[proposal_id, result\]
-        ProposalFinalized(ProposalId, dispatch::DispatchResult),
+		/// A proposal has been executed with the results of the calls, the calls after the failed
+		/// call were not executed and all changes were reverted. \[proposal_id, results\]
+        ProposalFinalized(ProposalId, Vec<dispatch::DispatchResult>),
         /// A proposal has been passed. \[proposal_id]
         ProposalPassed(ProposalId),
-        /// A proposal has been created.		\[organization_id, proposal_id, creator]
-        ProposalCreated(AccountId,ProposalId,AccountId),
+        /// A proposal has been created.		\[organization_id, proposal_id, creator, title, content]
+        ProposalCreated(AccountId,ProposalId,AccountId,Vec<u8>,Vec<u8>),
         /// Proposal refused or expired \[proposal_id]
         ProposalRefused(ProposalId),
+        /// Proposal was cancelled before it was finished \[proposal_id]
+        ProposalCancelled(ProposalId),
+        /// A passed proposal was queued, it will be executed at the block \[proposal_id, block_number]
+        ProposalQueued(ProposalId, BlockNumber),
+        /// A queued proposal was vetoed before it was executed by the guardian, or by the
+        /// `VetoOrigin` if it's `None` \[proposal_id, guardian]
+        ProposalVetoed(ProposalId, Option<AccountId>),
+        /// The guardian of the organization was set or removed \[organization_id, guardian]
+        GuardianUpdated(AccountId, Option<AccountId>),
+        /// The call whitelist of the organization was set or removed \[organization_id, whitelist]
+        CallWhitelistUpdated(AccountId, Option<Vec<CallFilter>>),
+        /// The enactment delay of the organization was updated \[organization_id, delay]
+        EnactmentDelayUpdated(AccountId, BlockNumber),
+        /// The rule param of the organization was updated \[organization_id, param]
+        OrgParamUpdated(AccountId, OrgRuleParam),
+        /// The vote of the voter was removed from the proposal \[proposal_id, voter]
+        VoteRemoved(ProposalId, AccountId),
+        /// A member was added into the organization \[organization_id, member]
+        MemberAdded(AccountId, AccountId),
+        /// A member left or was removed from the organization \[organization_id, member]
+        MemberRemoved(AccountId, AccountId),
+        /// A member delegated the voting power to another member \[organization_id, delegator, delegate, category, balance]
+        Delegated(AccountId, AccountId, AccountId, Option<CallCategory>, Balance),
+        /// A delegation was removed \[organization_id, delegator, category]
+        Undelegated(AccountId, AccountId, Option<CallCategory>),
+        /// A call was dispatched with the Root origin \[result]
+        DispatchedAsRoot(dispatch::DispatchResult),
 	}
 );
 
@@ -149,11 +327,50 @@ decl_error! {
 		/// it is not a member in the organization
 		NotMemberInOrg,
 		MemberDuplicate,
+		/// the last member can't leave the organization
+		LastMemberInOrg,
+		/// the organization only admits new members by the proposals
+		AdmissionNotOpen,
+		/// sponsoring new members is not allowed by the admission policy of the organization
+		NotSponsorAdmission,
 		/// not found the proposal by id in the runtime storage
 		ProposalNotFound,
 		ProposalDecodeFailed,
-		ProposalDuplicate,
 		ProposalExpired,
+		/// the proposal is neither expired nor passed, it can't be closed
+		ProposalNotFinished,
+		/// the voter has not voted on the proposal
+		VoteNotFound,
+		/// the member can't delegate the voting power to itself
+		DelegateToSelf,
+		/// not found the delegation of the call category
+		DelegationNotFound,
+		/// not found the queued proposal by id
+		ProposalNotQueued,
+		/// only the guardian of the organization can veto the proposal
+		NotGuardian,
+		/// the enactment delay must be nonzero while the organization has a guardian
+		GuardianNeedsDelay,
+		/// the proposals changing the guardian or the enactment delay can't be vetoed by the guardian
+		GuardianVetoNotAllowed,
+		/// the title of the proposal is longer than the `MaxTitleLength`
+		TitleTooLong,
+		/// the content of the proposal is longer than the `MaxContentLength`
+		ContentTooLong,
+		/// the proposal has more calls than the `MaxProposalCalls`
+		TooManyCalls,
+		/// the call is not allowed by the call whitelist of the organization
+		CallNotAllowed,
+		/// the dispatch weight of the calls is over the `MaxProposalWeight`
+		ProposalTooHeavy,
+		/// the weight bound is less than the dispatch weight of the calls of the proposal
+		WrongProposalWeight,
+		/// the delegate has reached the `MaxDelegatorsPerDelegate`
+		TooManyDelegators,
+		/// the organization has reached the `MaxMembers`
+		TooManyMembers,
+		/// only the creator of the proposal can do the operation
+		NotProposalCreator,
 		WrongRuleParam,
 	}
 }
@@ -162,25 +379,44 @@ decl_error! {
 // These functions materialize as "extrinsics", which are often compared to transactions.
 // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+	pub struct Module<T: Trait> for enum Call where origin: <T as frame_system::Trait>::Origin {
 		// Errors must be initialized if they are used by the pallet.
 		type Error = Error<T>;
 		const ModuleId: ModuleId = T::ModuleId::get();
 		const InherentStakeProposal: BalanceOf<T> = T::InherentStakeProposal::get();
+		const MaxExpiredPerBlock: u32 = T::MaxExpiredPerBlock::get();
+		/// The length of a vote locking period.
+		const VoteLockingPeriod: T::BlockNumber = T::VoteLockingPeriod::get();
+		const MaxEnactmentsPerBlock: u32 = T::MaxEnactmentsPerBlock::get();
+		const MaxEnactmentWeight: Weight = T::MaxEnactmentWeight::get();
+		const MaxTitleLength: u32 = T::MaxTitleLength::get();
+		const MaxContentLength: u32 = T::MaxContentLength::get();
+		const MaxProposalCalls: u32 = T::MaxProposalCalls::get();
+		const MaxDelegatorsPerDelegate: u32 = T::MaxDelegatorsPerDelegate::get();
+		const MaxMembers: u32 = T::MaxMembers::get();
+		const MaxProposalWeight: Weight = T::MaxProposalWeight::get();
 		// Events must be initialized if they are used by the pallet.
 		fn deposit_event() = default;
 
+		/// Close the expired proposals, unlock the voters' tokens and release the stake of the creators.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::close_expired_proposals(n).saturating_add(Self::enact_queued_proposals(n))
+		}
+
+		/// Migrate the organizations and the proposals of the first release, and set the meta
+		/// organization if the chain has none.
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v2::<T>().saturating_add(migration::set_meta_organization::<T>())
+		}
+
 		/// Create organization with the given organization info. Creator should set assetID=0 in
 		/// the `info`, new class of token with `total` amount will be created and assigned to the creator.
 		/// The organization id and number will be specified in the `OrganizationCreated` event.
-		#[weight = T::WeightInfo::create_organization(info.members.len() as u32)]
-		pub fn create_organization(origin, total: T::Balance, info: OrgInfoOf<T>) -> dispatch::DispatchResult {
+		/// The creator and the accounts in `members` are the initial members of the organization.
+		#[weight = T::WeightInfo::create_organization(members.len() as u32)]
+		pub fn create_organization(origin, total: T::Balance, info: OrgInfoOf<T>, members: Vec<T::AccountId>) -> dispatch::DispatchResult {
 			let owner = ensure_signed(origin)?;
-			let asset_id = Self::create_new_token(owner.clone(),total);
-			let mut info = info;
-			info.add_member(owner)?;
-			info.set_asset_id(asset_id);
-			Self::storage_new_organization(info)
+			Self::on_create_organization(owner, total, info, members)
 		}
 
 		/// Deposit `value` assets(IDV) to organization's vault, which will be assigned by proposals.
@@ -192,18 +428,70 @@ decl_module! {
 		}
 
 		/// Vote the proposal `pid`.
-		/// The proposal id `id` is specified in the `ProposalCreated` event.
+		/// The proposal id `(organization number, index)` is specified in the `ProposalCreated` event.
 		/// Note that only members in the organization can vote. To take `value` vote weight,
 		/// voter should lock `value` tokens. Tokens will be unlocked after the proposal is finish.
+		/// The `vote` can be `Aye`, `Nay` or `Abstain`, abstain votes are limited by the
+		/// `abstention` threshold of the rule. Voting again replaces the previous vote of the voter.
+		/// The `conviction` multiplies the vote weight, in exchange the tokens are kept locked for
+		/// the locking periods of the conviction after the proposal is finish.
 		/// And if the result is satisfied the rule, the proposal will be executed.
-		#[weight = T::WeightInfo::vote_proposal()]
-		pub fn vote_proposal(origin, pid: ProposalIdOf<T>, value: T::Balance, vote_for: bool) -> dispatch::DispatchResult {
+		/// The vote is charged for the `weight_bound` which must not be less than the weight of
+		/// the calls of the proposal, the weight of the calls is refunded unless the vote executed
+		/// the proposal.
+		#[weight = T::WeightInfo::vote_proposal().saturating_add(<Module<T>>::tally_weight())
+			.saturating_add(<Module<T>>::release_weight()).saturating_add(*weight_bound)]
+		pub fn vote_proposal(origin, pid: ProposalId, value: T::Balance, vote: Vote, conviction: Conviction,
+		#[compact] weight_bound: Weight) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_weight_bound(pid, weight_bound)?;
+			let proposal = Self::get_proposal_by_id(pid)?;
+			let tally = Self::proposal_tally_weight(&proposal)
+				.saturating_add(Self::release_weight_of((proposal.detail.votes.len() as u32).saturating_add(1)));
+			let used = Self::on_vote_proposal(pid, who, value, vote, conviction, frame_system::Module::<T>::block_number())?;
+			Ok(Some(T::WeightInfo::vote_proposal().saturating_add(tally).saturating_add(used)).into())
+		}
+
+		/// Remove the vote of the sender from the open proposal `pid`, the tokens locked by
+		/// the vote will be unlocked. The proposal may pass without the vote, so it's charged
+		/// for the `weight_bound` like `vote_proposal`.
+		#[weight = T::WeightInfo::remove_vote().saturating_add(<Module<T>>::tally_weight())
+			.saturating_add(<Module<T>>::release_weight()).saturating_add(*weight_bound)]
+		pub fn remove_vote(origin, pid: ProposalId, #[compact] weight_bound: Weight) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_weight_bound(pid, weight_bound)?;
+			let proposal = Self::get_proposal_by_id(pid)?;
+			let tally = Self::proposal_tally_weight(&proposal)
+				.saturating_add(Self::release_weight_of((proposal.detail.votes.len() as u32).saturating_add(1)));
+			let used = Self::on_remove_vote(pid, who, frame_system::Module::<T>::block_number())?;
+			Ok(Some(T::WeightInfo::remove_vote().saturating_add(tally).saturating_add(used)).into())
+		}
+
+		/// Delegate the voting power of `value` tokens to the member `target` of the organization `id`,
+		/// the tokens are locked until undelegated(no tokens are locked in the `Membership` voting mode). With the `category`(the index of the pallet
+		/// in the runtime) the delegation only applies to the proposals of the calls in the category,
+		/// otherwise it applies to all proposals. The delegated tokens are counted in the votes of
+		/// the delegate, unless the sender votes on the proposal directly.
+		/// Note that the `id` is the organization number, not organization id.
+		#[weight = T::WeightInfo::delegate()]
+		pub fn delegate(origin, id: u32, target: <T::Lookup as StaticLookup>::Source,
+		category: Option<CallCategory>, value: T::Balance) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			Self::on_vote_proposal(pid, who, value, vote_for, frame_system::Module::<T>::block_number())
+			let target = T::Lookup::lookup(target)?;
+			Self::on_delegate(id, who, target, category, value)
+		}
+
+		/// Remove the delegation of the `category` in the organization `id`, the delegated tokens
+		/// will be unlocked.
+		#[weight = T::WeightInfo::undelegate()]
+		pub fn undelegate(origin, id: u32, category: Option<CallCategory>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::on_undelegate(id, who, category)
 		}
 
 		/// Add a new member to the organization and assign tokens to the new member.
-		/// All members in the organization `id` can add accounts `target` into the organization.
+		/// All members in the organization `id` can add accounts `target` into the organization
+		/// if the admission policy of the organization is `Open`.
 		/// The member can assign `assigned_value` tokens to the new member.
 		/// Note that the `id` is the organization number, not organization id.
 		#[weight = T::WeightInfo::add_member_and_assign_token()]
@@ -215,6 +503,105 @@ decl_module! {
 			Self::on_add_member_and_assign_token(owner, who, id, assigned_value)
 		}
 
+		/// Add the account `target` into the organization, it can only be called by the
+		/// organization itself, that is executed by a passed proposal.
+		#[weight = T::WeightInfo::add_member()]
+		pub fn add_member(origin, target: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			let oid = Self::ensure_organization(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			Self::on_add_member(oid, who)
+		}
+
+		/// Update the rule param of the organization, it can only be called by the organization
+		/// itself, that is executed by a passed proposal. The open proposals keep the `sub_param`
+		/// they were created with.
+		#[weight = T::WeightInfo::update_org_param()]
+		pub fn update_org_param(origin, param: OrgRuleParamOf<T>) -> dispatch::DispatchResult {
+			let oid = Self::ensure_organization(origin)?;
+			Self::on_update_org_param(oid, param)
+		}
+
+		/// Set or remove the call whitelist of the organization, it can only be called by the
+		/// organization itself, that is executed by a passed proposal. The new proposals can only
+		/// contain the calls allowed by the whitelist, all calls are allowed without it.
+		#[weight = T::WeightInfo::set_call_whitelist()]
+		pub fn set_call_whitelist(origin, whitelist: Option<Vec<CallFilter>>) -> dispatch::DispatchResult {
+			let oid = Self::ensure_organization(origin)?;
+			Self::on_set_call_whitelist(oid, whitelist)
+		}
+
+		/// Dispatch the `call` with the Root origin, such as the runtime upgrade, it can only be
+		/// called by the `RootOrigin`. The `weight` is charged for the `call` instead of the
+		/// dispatch weight of the `call`, so the heavy calls can be executed by a proposal.
+		#[weight = (T::WeightInfo::dispatch_as_root().saturating_add(*weight), call.get_dispatch_info().class)]
+		pub fn dispatch_as_root(origin, call: Box<<T as Trait>::Call>, weight: Weight) -> dispatch::DispatchResult {
+			T::RootOrigin::ensure_origin(origin)?;
+			let root: <T as frame_system::Trait>::Origin = frame_system::RawOrigin::Root.into();
+			let res = call.dispatch(<T as Trait>::Origin::from(root));
+			Self::deposit_event(RawEvent::DispatchedAsRoot(res.map(|_| ()).map_err(|e| e.error)));
+			Ok(())
+		}
+
+		/// Dispatch the `call` with the signed origin of the organization id, it can only be called
+		/// by the organization itself, that is executed by a passed proposal. The calls of the
+		/// proposals are dispatched with the organization origin, so the calls of other pallets
+		/// which require a signed origin, such as the transfers from the organization account,
+		/// must be wrapped in it.
+		#[weight = (T::WeightInfo::dispatch_as_account().saturating_add(call.get_dispatch_info().weight), call.get_dispatch_info().class)]
+		pub fn dispatch_as_account(origin, call: Box<<T as Trait>::Call>) -> dispatch::DispatchResult {
+			let oid = Self::ensure_organization(origin)?;
+			let signed: <T as frame_system::Trait>::Origin = frame_system::RawOrigin::Signed(oid).into();
+			call.dispatch(<T as Trait>::Origin::from(signed)).map(|_| ()).map_err(|e| e.error)
+		}
+
+		/// Set the blocks between passing and executing the proposals of the organization, it can
+		/// only be called by the organization itself, that is executed by a passed proposal.
+		/// The passed proposals are queued during the delay, and they are executed immediately
+		/// if the delay is zero, which is not allowed while the organization has a guardian.
+		#[weight = T::WeightInfo::set_enactment_delay()]
+		pub fn set_enactment_delay(origin, delay: T::BlockNumber) -> dispatch::DispatchResult {
+			let oid = Self::ensure_organization(origin)?;
+			Self::on_set_enactment_delay(oid, delay)
+		}
+
+		/// Veto the queued proposal `pid` before it was executed, it can only be called by the
+		/// `VetoOrigin` or the guardian of the organization. The proposals of `set_guardian` and
+		/// `set_enactment_delay` can only be vetoed by the `VetoOrigin`.
+		#[weight = T::WeightInfo::veto_proposal()]
+		pub fn veto_proposal(origin, pid: ProposalId) -> dispatch::DispatchResult {
+			let guardian = match T::VetoOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			Self::on_veto_proposal(pid, guardian)
+		}
+
+		/// Set or remove the guardian of the organization, it can only be called by the
+		/// organization itself, that is executed by a passed proposal. The guardian can veto
+		/// the passed proposals during the enactment delay, so the delay must be set before.
+		#[weight = T::WeightInfo::set_guardian()]
+		pub fn set_guardian(origin, guardian: Option<<T::Lookup as StaticLookup>::Source>) -> dispatch::DispatchResult {
+			let oid = Self::ensure_organization(origin)?;
+			let guardian = match guardian {
+				Some(target) => Some(T::Lookup::lookup(target)?),
+				None => None,
+			};
+			Self::on_set_guardian(oid, guardian)
+		}
+
+		/// Sponsor the account `target` to join the organization `id` by creating a proposal
+		/// of `add_member`, it was only allowed by the `SponsorVote` admission policy. The
+		/// proposal is voted by the rule param of the organization and ends after `length` blocks.
+		/// Note that the `id` is the organization number, not organization id.
+		#[weight = T::WeightInfo::create_proposal()]
+		pub fn sponsor_member(origin, id: u32, target: <T::Lookup as StaticLookup>::Source,
+		length: T::BlockNumber) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			let expire = frame_system::Module::<T>::block_number().saturating_add(length);
+			Self::on_sponsor_member(id, who, target, expire)
+		}
+
 		/// Create a proposal to vote. The creator must be the member of the organization,
 		/// and to prevent "spamming", creating a new proposal could require some assets(The quantity 
 		/// is specified by `InherentStakeProposal`).
@@ -224,13 +611,62 @@ decl_module! {
 		/// Note that the `id` is the organization number, not organization id,The successful 
 		/// creation of the proposal will lock some assets, and the closing of the proposal
 		/// will unlock the assets.
+		/// The `calls`(up to `MaxProposalCalls`) are executed in order as the organization origin
+		/// after the proposal passed, they are all reverted if any of them failed.
+		/// The `title`(up to `MaxTitleLength` bytes) and the `content`(the hash or the CID of the
+		/// off-chain document with the full rationale, up to `MaxContentLength` bytes) are stored
+		/// on the proposal and given in the `ProposalCreated` event.
 		#[weight = T::WeightInfo::create_proposal()]
 		pub fn create_proposal(origin, id: u32, length: T::BlockNumber, sub_param: OrgRuleParamOf<T>,
-		call: Box<<T as Trait>::Call>) -> dispatch::DispatchResult {
+		calls: Vec<<T as Trait>::Call>, title: Vec<u8>, content: Vec<u8>) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
 			let cur = frame_system::Module::<T>::block_number();
 			let expire = cur.saturating_add(length);
-			Self::on_create_proposal(id,who,expire,sub_param,call)
+			Self::on_create_proposal(id,who,expire,sub_param,calls,title,content)
+		}
+
+		/// Close the proposal `pid` which was expired or passed, anyone can close the proposal.
+		/// The passed proposal will be executed, the voters' tokens will be unlocked and
+		/// the stake of the creator will be released. It's charged for the `weight_bound` like
+		/// `vote_proposal`.
+		#[weight = T::WeightInfo::close_proposal().saturating_add(<Module<T>>::tally_weight())
+			.saturating_add(<Module<T>>::release_weight()).saturating_add(*weight_bound)]
+		pub fn close_proposal(origin, pid: ProposalId, #[compact] weight_bound: Weight) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::ensure_weight_bound(pid, weight_bound)?;
+			Self::on_close_proposal(pid, frame_system::Module::<T>::block_number())
+		}
+
+		/// Cancel the proposal `pid` before it was finished, only the creator(or the authority
+		/// defined by the rule) can cancel the proposal. The voters' tokens will be unlocked and
+		/// the stake of the creator will be released.
+		#[weight = T::WeightInfo::cancel_proposal()]
+		pub fn cancel_proposal(origin, pid: ProposalId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::on_cancel_proposal(pid, who, frame_system::Module::<T>::block_number())
+		}
+
+		/// Leave the organization `id`, the votes of the member on the open proposals will be
+		/// retracted and the proposals created by the member will be cancelled, the locked
+		/// tokens will be unlocked. The delegations of the member and the delegations to the member
+		/// will be removed. The member still keeps the tokens of the organization.
+		/// Note that the `id` is the organization number, not organization id.
+		#[weight = T::WeightInfo::leave_organization().saturating_add(<Module<T>>::undelegate_all_weight())]
+		pub fn leave_organization(origin, id: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let undelegated = Self::on_remove_member(Self::counter_2_orgid(id), who)?;
+			Ok(Some(T::WeightInfo::leave_organization().saturating_add(undelegated)).into())
+		}
+
+		/// Remove the member `target` from the organization, it can only be called by the
+		/// organization itself, that is executed by a passed proposal. The member was
+		/// handled as same as `leave_organization`.
+		#[weight = T::WeightInfo::remove_member().saturating_add(<Module<T>>::undelegate_all_weight())]
+		pub fn remove_member(origin, target: <T::Lookup as StaticLookup>::Source) -> DispatchResultWithPostInfo {
+			let oid = Self::ensure_organization(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			let undelegated = Self::on_remove_member(oid, who)?;
+			Ok(Some(T::WeightInfo::remove_member().saturating_add(undelegated)).into())
 		}
 
 		/// Transfer the assets(IDV) from the vault of the organization to the dest account.
@@ -240,7 +676,7 @@ decl_module! {
 						origin,
 		        		dest: <T::Lookup as StaticLookup>::Source,
 						#[compact] value: T::Balance) -> dispatch::DispatchResult {
-			let send = ensure_signed(origin)?;
+			let send = Self::ensure_organization(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
 			Self::handle_transfer_by_decision(send, dest, value)
 		}
@@ -256,13 +692,9 @@ impl<T: Trait> Module<T>  {
 	pub fn counter_of() -> OrgCount {
 		OrgCounter::get()
 	}
-	/// Get the count of the proposals in storage
-	pub fn count_of_proposals() -> u32 {
-		<Proposals<T>>::iter().map(|(v, _)| v).count() as u32
-	}
-	/// Get the count of the proposals in storage
+	/// Get the count of the organizations in storage
 	pub fn count_of_organizations() -> u32 {
-		<OrgInfos<T>>::iter().map(|(v, _)| v).count() as u32
+		OrgCounter::get()
 	}
 	pub fn get_orginfo_by_id(oid: T::AccountId) -> Result<OrgInfoOf<T>, dispatch::DispatchError> {
 		if OrgInfos::<T>::contains_key(oid.clone()) {
@@ -276,13 +708,25 @@ impl<T: Trait> Module<T>  {
 	}
 	/// Check whether the user belongs to the organization
 	pub fn is_member(oid: T::AccountId, who: &T::AccountId) -> bool {
-		match <OrgInfos<T>>::get(oid) {
-			Some(val) => val.is_member(who.clone()),
-			None => false,
+		Members::<T>::contains_key(oid, who)
+	}
+	/// Ensure the origin was the organization, which is the origin of the passed proposals.
+	pub fn ensure_organization(origin: <T as frame_system::Trait>::Origin) -> Result<T::AccountId, dispatch::DispatchError> {
+		match <T as Trait>::Origin::from(origin).into() {
+			Ok(RawOrigin::Organization(oid, _)) => Ok(oid),
+			Err(_) => Err(dispatch::DispatchError::BadOrigin),
 		}
 	}
+	/// Decode the encoded calls of the proposal
+	pub fn decode_calls(call: &[u8]) -> Result<Vec<<T as Trait>::Call>, dispatch::DispatchError> {
+		Vec::<<T as Trait>::Call>::decode(&mut &call[..]).map_err(|_| Error::<T>::ProposalDecodeFailed.into())
+	}
+	/// The id of the next proposal created in the organization `id`
+	pub fn next_proposal_id(id: OrgCount) -> ProposalId {
+		(id, ProposalCounter::get(id))
+	}
 	/// Get the info of proposal `pid`
-	pub fn get_proposal_by_id(pid: ProposalIdOf<T>) -> Result<ProposalOf<T>, dispatch::DispatchError> {
+	pub fn get_proposal_by_id(pid: ProposalId) -> Result<ProposalOf<T>, dispatch::DispatchError> {
 		match Proposals::<T>::get(pid) {
 			Some(proposal) => Ok(proposal),
 			None => Err(Error::<T>::ProposalNotFound.into()),
@@ -290,68 +734,147 @@ impl<T: Trait> Module<T>  {
 	}
 
 	/// Storage the info of the new created organization
-	fn storage_new_organization(oinfo: OrgInfoOf<T>) -> dispatch::DispatchResult {
+	fn storage_new_organization(oinfo: OrgInfoOf<T>, members: Vec<T::AccountId>) -> dispatch::DispatchResult {
 		let counter = OrgCounter::get();
 		let new_counter = counter.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
 		let oid = Self::counter_2_orgid(counter);
 
 		OrgInfos::<T>::insert(&oid, oinfo.clone());
+		for member in members {
+			Self::base_add_member_by_orgid(oid.clone(), member)?;
+		}
 		Self::deposit_event(RawEvent::OrganizationCreated(oid, counter, oinfo));
 		OrgCounter::put(new_counter);
 		Ok(())
 	}
 
-	fn base_create_proposal(oid: T::AccountId,proposal: ProposalOf<T>) -> dispatch::DispatchResult {
+	/// Storage the new proposal of the organization `id` with the next sequential index
+	fn base_create_proposal(id: OrgCount,proposal: ProposalOf<T>) -> Result<ProposalId, dispatch::DispatchError> {
+		let proposal_id = Self::next_proposal_id(id);
+		let next_index = proposal_id.1.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
 
-		let proposal_id = Self::make_proposal_id(&proposal);
-		if Proposals::<T>::contains_key(proposal_id) {
-			return Err(Error::<T>::ProposalDuplicate.into());
-		}
 		Proposals::<T>::insert(&proposal_id, proposal.clone());
-		Self::deposit_event(RawEvent::ProposalCreated(oid, proposal_id,proposal.creator()));
-		Ok(())
+		ProposalCounter::insert(id, next_index);
+		ExpiringProposals::<T>::mutate(proposal.detail.end_dt.clone(), |pids| pids.push(proposal_id));
+		OrgProposals::<T>::mutate(proposal.org.clone(), |pids| pids.push(proposal_id));
+		ProposalCount::mutate(|count| *count = count.saturating_add(1));
+		Self::deposit_event(RawEvent::ProposalCreated(proposal.org.clone(), proposal_id,proposal.creator(),
+			proposal.title, proposal.content));
+		Ok(proposal_id)
 	}
 
 	/// Add a member into the organization by org id
 	fn base_add_member_by_orgid(oid: T::AccountId, member_id: T::AccountId) -> dispatch::DispatchResult {
-		OrgInfos::<T>::try_mutate(oid,|infos| -> dispatch::DispatchResult {
-			match infos {
-				Some(org) => {match org.members
-					.iter()
-					.find(|&x| *x==member_id) {
-					None => {
-						org.members.push(member_id);
-						Ok(())
-					},
-					_ => Ok(())
-				}},
-				None => Ok(()),
+		if !Members::<T>::contains_key(&oid, &member_id) {
+			ensure!(Self::get_count_members(oid.clone()) < T::MaxMembers::get(), Error::<T>::TooManyMembers);
+			let record = MemberRecord { joined: frame_system::Module::<T>::block_number() };
+			Members::<T>::insert(&oid, &member_id, record);
+			MemberCount::<T>::mutate(oid, |count| *count = count.saturating_add(1));
+		}
+		Ok(())
+	}
+
+	/// Remove the proposal from the storage, unlock the voters' tokens and release the stake
+	/// of the creator. The tokens of the conviction votes are kept locked for the periods of
+	/// the conviction.
+	fn base_release_proposal(oid: T::AccountId, aid: T::TokenId, pid: ProposalId, proposal: &ProposalOf<T>) -> dispatch::DispatchResult {
+		Self::remove_proposal_by_id(pid);
+		let now = frame_system::Module::<T>::block_number();
+		proposal.detail.votes.iter().filter(|(_,(value,_,_))| !value.is_zero()).for_each(|(voter,(value,_,conviction))|{
+			let periods: T::BlockNumber = conviction.lock_periods().into();
+			let until = now.saturating_add(T::VoteLockingPeriod::get().saturating_mul(periods));
+			match T::TokenHandler::unlock_at(aid, voter, *value, until) {
+				_ => {},
 			}
+		});
+		let proposal_creator = proposal.creator();
+		let locked_balance = T::InherentStakeProposal::get();
+		T::Finance::unlock_balance(oid,proposal_creator,locked_balance)
+	}
+
+	/// Remove a member from the organization by org id
+	fn base_remove_member_by_orgid(oid: T::AccountId, member_id: T::AccountId) -> dispatch::DispatchResult {
+		if Members::<T>::take(&oid, &member_id).is_some() {
+			MemberCount::<T>::mutate(oid, |count| *count = count.saturating_sub(1));
+		}
+		Ok(())
+	}
+
+	/// Retract the vote of the `voter` on the proposal and unlock the voter's tokens
+	fn base_retract_vote(aid: T::TokenId, pid: ProposalId, voter: T::AccountId) -> dispatch::DispatchResult {
+		Proposals::<T>::try_mutate(pid,|proposal| -> dispatch::DispatchResult {
+			if let Some(p) = proposal {
+				if let Some((value,_,_)) = p.detail.remove_vote(&voter) {
+					if !value.is_zero() {
+						T::TokenHandler::unlock(aid, &voter, value)?;
+					}
+				}
+			}
+			Ok(())
 		})
 	}
 
-	fn remove_proposal_by_id(pid: ProposalIdOf<T>) {
-		Proposals::<T>::remove(pid);
+	fn remove_proposal_by_id(pid: ProposalId) {
+		if let Some(proposal) = Proposals::<T>::take(pid) {
+			ExpiringProposals::<T>::mutate_exists(proposal.detail.end_dt, |maybe_pids| {
+				if let Some(pids) = maybe_pids {
+					pids.retain(|x| *x != pid);
+					if pids.is_empty() {
+						*maybe_pids = None;
+					}
+				}
+			});
+			OrgProposals::<T>::mutate_exists(proposal.org, |maybe_pids| {
+				if let Some(pids) = maybe_pids {
+					pids.retain(|x| *x != pid);
+					if pids.is_empty() {
+						*maybe_pids = None;
+					}
+				}
+			});
+			ProposalCount::mutate(|count| *count = count.saturating_sub(1));
+		}
 	}
 
 	/// Add vote infos in the proposal item
-	fn base_vote_on_proposal(pid: ProposalIdOf<T>, voter: T::AccountId,
-								 value: BalanceOf<T>, vote_for: bool) -> dispatch::DispatchResult {
+	fn base_vote_on_proposal(pid: ProposalId, voter: T::AccountId,
+								 value: BalanceOf<T>, vote: Vote, conviction: Conviction) -> dispatch::DispatchResult {
 		Proposals::<T>::try_mutate(pid,|proposal| -> dispatch::DispatchResult {
 			if let Some(p) = proposal {
-				p.detail.vote(voter.clone(),value, vote_for);
-				// *proposal = Some(p);
+				p.detail.vote(voter.clone(),value, vote, conviction);
 			};
 			Ok(())
 		})?;
 		Ok(())
 	}
-	fn base_call_dispatch(pid: ProposalIdOf<T>,proposal: ProposalOf<T>) -> dispatch::DispatchResult {
-		// remove the proposal from the storage by the proposal passed
-		let call = <T as Trait>::Call::decode(&mut &proposal.call[..]).map_err(|_| Error::<T>::ProposalDecodeFailed)?;
-		let res = call.dispatch(frame_system::RawOrigin::Signed(proposal.org).into());
-		Self::deposit_event(RawEvent::ProposalFinalized(pid, res.map(|_| ()).map_err(|e| e.error)));
-		Ok(())
+	/// Execute the calls of the proposal in order as the organization origin, all changes of
+	/// the calls are reverted if any of them failed.
+	fn base_call_dispatch(pid: ProposalId,proposal: ProposalOf<T>) -> Result<Weight, dispatch::DispatchError> {
+		let calls = Self::decode_calls(&proposal.call)?;
+		let tally = Self::proposal_tally(&proposal);
+		let org = proposal.org;
+		let mut weight: Weight = 0;
+		let results = with_transaction(|| {
+			let mut results = Vec::new();
+			for call in calls.into_iter() {
+				let info = call.get_dispatch_info();
+				let res = call.dispatch(RawOrigin::Organization(org.clone(), tally).into());
+				let post_info = match &res {
+					Ok(post_info) => *post_info,
+					Err(err) => err.post_info,
+				};
+				weight = weight.saturating_add(post_info.calc_actual_weight(&info));
+				let res = res.map(|_| ()).map_err(|e| e.error);
+				let failed = res.is_err();
+				results.push(res);
+				if failed {
+					return TransactionOutcome::Rollback(results);
+				}
+			}
+			TransactionOutcome::Commit(results)
+		});
+		Self::deposit_event(RawEvent::ProposalFinalized(pid, results));
+		Ok(weight)
 	}
 }
 
@@ -367,15 +890,18 @@ mod test {
 		codec::{Encode},impl_outer_origin,
 		impl_outer_dispatch, assert_ok, assert_noop, parameter_types, weights::Weight};
 	use sp_core::H256;
-	use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup,Hash}, testing::Header,ModuleId};
+	use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header,ModuleId};
 	use pallet_balances;
 	use organization::{Proposal};
 	use rules::{OrgRuleParam};
+	use crate as idavoll;
 	use sp_std::{prelude::Vec, boxed::Box,collections::btree_map::BTreeMap};
 
 
 	impl_outer_origin! {
-		pub enum Origin for Test where system = frame_system {}
+		pub enum Origin for Test where system = frame_system {
+			idavoll<T>,
+		}
 	}
 	impl_outer_dispatch! {
 		pub enum Call for Test where origin: Origin {
@@ -399,6 +925,8 @@ mod test {
 		pub const MaximumBlockLength: u32 = 2 * 1024;
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
 		pub const IdvAssetModuleId: ModuleId = ModuleId(*b"py/asset");
+		pub const MaxUnlocksPerBlock: u32 = 50;
+		pub const MaxUnlockDelay: u32 = 10;
 		pub const IdavollModuleId: ModuleId = ModuleId(*b"py/idvol");
 	}
 	impl frame_system::Trait for Test {
@@ -432,6 +960,16 @@ mod test {
 	parameter_types! {
 	pub const ExistentialDeposit: u64 = 10;
 	pub const InherentStakeProposal: u64 = 1;
+	pub const MaxExpiredPerBlock: u32 = 2;
+	pub const VoteLockingPeriod: u64 = 10;
+	pub const MaxEnactmentsPerBlock: u32 = 2;
+	pub const MaxEnactmentWeight: Weight = 250_000;
+	pub const MaxTitleLength: u32 = 16;
+	pub const MaxContentLength: u32 = 64;
+	pub const MaxProposalCalls: u32 = 4;
+	pub const MaxProposalWeight: Weight = 200_000_000_000;
+	pub const MaxDelegatorsPerDelegate: u32 = 2;
+	pub const MaxMembers: u32 = 100;
     }
 	impl pallet_balances::Trait for Test {
 		type Balance = u64;
@@ -449,27 +987,44 @@ mod test {
 		type AssetId = u32;
 		type Currency = IdvBalances;
 		type ModuleId = IdvAssetModuleId;
+		type MaxUnlocksPerBlock = MaxUnlocksPerBlock;
+		type MaxUnlockDelay = MaxUnlockDelay;
 	}
 
 	type IdavollModule = Module<Test>;
 	type IdavallCall = super::Call<Test>;
 	impl Trait for Test {
 		type Event = ();
+		type Origin = Origin;
 		type Call = Call;
 		type Balance = u64;
 		type TokenId = u32;
 		type ModuleId = IdavollModuleId;
 		type TokenHandler = IdavollAsset;
 		type Finance = IdavollAsset;
+		type RuleHandler = DefaultRule<Test>;
 		type InherentStakeProposal = InherentStakeProposal;
+		type MaxExpiredPerBlock = MaxExpiredPerBlock;
+		type VoteLockingPeriod = VoteLockingPeriod;
+		type MaxEnactmentsPerBlock = MaxEnactmentsPerBlock;
+		type MaxEnactmentWeight = MaxEnactmentWeight;
+		type MaxTitleLength = MaxTitleLength;
+		type MaxContentLength = MaxContentLength;
+		type MaxProposalCalls = MaxProposalCalls;
+		type MaxProposalWeight = MaxProposalWeight;
+		type MaxDelegatorsPerDelegate = MaxDelegatorsPerDelegate;
+		type MaxMembers = MaxMembers;
+		type VetoOrigin = frame_system::EnsureRoot<u128>;
+		type MetaOrgNumber = ();
+		type RootOrigin = frame_system::EnsureRoot<u128>;
 		type WeightInfo = ();
 	}
 
 	fn make_transfer_fail_proposal(value: u64) -> Vec<u8> {
-		Call::IdvBalances(pallet_balances::Call::transfer(RECEIVER.clone(), value)).encode()
+		vec![Call::IdvBalances(pallet_balances::Call::transfer(RECEIVER.clone(), value))].encode()
 	}
 	fn make_transfer_proposal(value: u64) -> Vec<u8> {
-		Call::IdavollModule(IdavallCall::vault_transfer(RECEIVER.clone(),value)).encode()
+		vec![Call::IdavollModule(IdavallCall::vault_transfer(RECEIVER.clone(),value))].encode()
 	}
 	// fn make_system_proposal(_value: u64) -> Vec<u8> {
 	// 	Call::System(frame_system::Call::remark(vec![0; 1])).encode()
@@ -482,6 +1037,9 @@ mod test {
 			org:    oid.clone(),
 			call: 	make_transfer_fail_proposal(value),
 			detail: ProposalDetail::new(owner.clone(),5,sub_param.clone()),
+			title: vec![],
+			content: vec![],
+			weight: 0,
 		}
 	}
 	fn create_proposal2(call: Vec<u8>) -> ProposalOf<Test> {
@@ -490,6 +1048,9 @@ mod test {
 			org:    ORGID.clone(),
 			call: 	call.clone(),
 			detail: ProposalDetail::new(OWNER.clone(),5,sub_param.clone()),
+			title: vec![],
+			content: vec![],
+			weight: 0,
 		}
 	}
 	fn create_proposal3(id: u128,call: Vec<u8>) -> ProposalOf<Test> {
@@ -498,6 +1059,9 @@ mod test {
 			org:    id.clone(),
 			call: 	call.clone(),
 			detail: ProposalDetail::new(OWNER.clone(),5,sub_param.clone()),
+			title: vec![],
+			content: vec![],
+			weight: 0,
 		}
 	}
 
@@ -520,11 +1084,11 @@ mod test {
 	#[test]
 	fn base_organization_01_should_work() {
 		new_test_ext().execute_with(|| {
-			let mut org = create_org(vec![OWNER,1,2,3]);
+			let mut org = create_org();
 			let asset_id = IdavollModule::create_new_token(OWNER.clone(),100);
 			assert_eq!(asset_id,0);
 			org.set_asset_id(asset_id.clone());
-			assert_ok!(IdavollModule::storage_new_organization(org.clone()));
+			assert_ok!(IdavollModule::storage_new_organization(org.clone(),vec![OWNER,1,2,3]));
 			assert_eq!(IdavollModule::get_orginfo_by_id(IdavollModule::counter_2_orgid(0)),Ok(org.clone()));
 
 			assert_eq!(IdavollModule::is_member(IdavollModule::counter_2_orgid(0),&OWNER),true);
@@ -532,23 +1096,84 @@ mod test {
 			assert_eq!(IdavollModule::is_member(IdavollModule::counter_2_orgid(0),&9),false);
 
 			for _i in 0..100 {
-				let org = create_org(vec![OWNER,1,2,3]);
-				assert_ok!(IdavollModule::storage_new_organization(org.clone()));
+				let org = create_org();
+				assert_ok!(IdavollModule::storage_new_organization(org.clone(),vec![OWNER,1,2,3]));
 			}
 			assert_eq!(IdavollModule::count_of_organizations(),100+1);
 		});
 	}
 
+	#[test]
+	fn base_members_migration_should_work() {
+		new_test_ext().execute_with(|| {
+			let mut org = create_org();
+			org.set_asset_id(1);
+			let oid = IdavollModule::counter_2_orgid(0);
+			OrgCounter::put(1);
+			// the organization of the first release with the members ahead of the param and the asset
+			let key = OrgInfos::<Test>::hashed_key_for(&oid);
+			let old = (vec![OWNER,1,2], (60u32,5u32,0u32), 1u32);
+			frame_support::storage::unhashed::put(&key, &old);
+			// the proposal of the first release was keyed by the hash with the bool votes
+			let call = Call::IdavollModule(crate::Call::vault_transfer(RECEIVER.clone(),10));
+			let mut votes = BTreeMap::new();
+			votes.insert(OWNER,(30u64,true));
+			votes.insert(1,(20u64,false));
+			let old_proposal = (oid.clone(), call.encode(), votes, OWNER, 5u64, (60u32,5u32,0u32));
+			let hash = H256::repeat_byte(1);
+			let mut key = sp_io::hashing::twox_128(b"IdavollModule").to_vec();
+			key.extend(&sp_io::hashing::twox_128(b"Proposals"));
+			key.extend(&sp_io::hashing::blake2_128(&hash.encode()));
+			key.extend(&hash.encode());
+			frame_support::storage::unhashed::put(&key, &old_proposal);
+			// the proposal which was expired before the upgrade
+			let expired = (oid.clone(), call.encode(), BTreeMap::<u128,(u64,bool)>::new(), OWNER, 2u64, (60u32,5u32,0u32));
+			let hash = H256::repeat_byte(2);
+			let mut expired_key = sp_io::hashing::twox_128(b"IdavollModule").to_vec();
+			expired_key.extend(&sp_io::hashing::twox_128(b"Proposals"));
+			expired_key.extend(&sp_io::hashing::blake2_128(&hash.encode()));
+			expired_key.extend(&hash.encode());
+			frame_support::storage::unhashed::put(&expired_key, &expired);
+			System::set_block_number(3);
+			assert_eq!(IdavollModule::storage_version(),Releases::V1);
+
+			migration::migrate_to_v2::<Test>();
+			assert_eq!(IdavollModule::storage_version(),Releases::V2);
+			assert_eq!(IdavollModule::get_orginfo_by_id(oid.clone()),Ok(org.clone()));
+			assert_eq!(IdavollModule::get_count_members(oid.clone()),3);
+			assert_eq!(IdavollModule::is_member(oid.clone(),&2),true);
+			assert_eq!(IdavollModule::is_member(oid.clone(),&3),false);
+			assert_eq!(frame_support::storage::unhashed::get_raw(&key),None);
+			assert_eq!(IdavollModule::org_proposals(oid.clone()).len(),2);
+			let pid = IdavollModule::expiring_proposals(5)[0];
+			let proposal = IdavollModule::get_proposal_by_id(pid).unwrap();
+			assert_eq!(IdavollModule::decode_calls(&proposal.call),Ok(vec![call]));
+			assert_eq!(proposal.detail.votes.get(&OWNER),Some(&(30,Vote::Aye,Conviction::None)));
+			assert_eq!(proposal.detail.votes.get(&1),Some(&(20,Vote::Nay,Conviction::None)));
+			assert_eq!(proposal.detail.sub_param,OrgRuleParam::new(60,5,0));
+			// the expired proposal is indexed at the next block, so it's closed automatically
+			let expired_pid = IdavollModule::expiring_proposals(4)[0];
+			assert_eq!(IdavollModule::get_proposal_by_id(expired_pid).unwrap().detail.end_dt,4);
+			System::set_block_number(5);
+			<IdavollModule as frame_support::traits::OnInitialize<u64>>::on_initialize(5);
+			assert_eq!(IdavollModule::get_proposal_by_id(expired_pid).is_err(),true);
+			assert_eq!(IdavollModule::org_proposals(oid.clone()),vec![pid]);
+			// the migration only runs once
+			migration::migrate_to_v2::<Test>();
+			assert_eq!(IdavollModule::get_orginfo_by_id(oid.clone()),Ok(org.clone()));
+		});
+	}
+
 	#[test]
 	fn base_organization_02_should_work() {
 
 		new_test_ext().execute_with(|| {
-			let mut org = create_org(vec![OWNER,1,2,3]);
+			let mut org = create_org();
 			let asset_id = IdavollModule::create_new_token(OWNER.clone(),100);
 			assert_eq!(asset_id,0);
 			org.set_asset_id(asset_id.clone());
 			let org_id = IdavollModule::counter_2_orgid(0);
-			assert_ok!(IdavollModule::storage_new_organization(org.clone()));
+			assert_ok!(IdavollModule::storage_new_organization(org.clone(),vec![OWNER,1,2,3]));
 			assert_eq!(IdavollModule::get_orginfo_by_id(org_id),Ok(org.clone()));
 			assert_eq!(IdavollModule::get_count_members(org_id),4);
 			// add member for the organization
@@ -574,14 +1199,15 @@ mod test {
 		new_test_ext().execute_with(|| {
 			let org_id = IdavollModule::counter_2_orgid(0);
 			let proposal = create_proposal(org_id,20,OWNER);
-			assert_ok!(IdavollModule::base_create_proposal(org_id,proposal.clone()));
-			// storage proposal repeat
-			assert_noop!(IdavollModule::base_create_proposal(org_id,proposal.clone()),Error::<Test>::ProposalDuplicate);
-			let proposal_id = IdavollModule::make_proposal_id(&proposal.clone());
-			print!("{}",proposal_id);
+			let proposal_id = IdavollModule::base_create_proposal(0,proposal.clone()).unwrap();
+			assert_eq!(proposal_id,(0,0));
+			// the same proposal can be created again with the next index
+			assert_eq!(IdavollModule::base_create_proposal(0,proposal.clone()),Ok((0,1)));
+			assert_eq!(IdavollModule::next_proposal_id(0),(0,2));
+			assert_eq!(IdavollModule::next_proposal_id(1),(1,0));
 			assert_eq!(IdavollModule::get_proposal_by_id(proposal_id),Ok(proposal.clone()));
-			let pid2 = <Test as frame_system::Trait>::Hashing::hash_of(&0);
-			assert_noop!(IdavollModule::get_proposal_by_id(pid2),Error::<Test>::ProposalNotFound);
+			assert_eq!(IdavollModule::get_proposal_by_id((0,1)),Ok(proposal.clone()));
+			assert_noop!(IdavollModule::get_proposal_by_id((1,0)),Error::<Test>::ProposalNotFound);
 			// remove proposal
 			IdavollModule::remove_proposal_by_id(proposal_id);
 			assert_noop!(IdavollModule::get_proposal_by_id(proposal_id),Error::<Test>::ProposalNotFound);
@@ -596,8 +1222,7 @@ mod test {
 			for i in 0..10 {
 				let org_id = IdavollModule::counter_2_orgid(i);
 				let proposal = create_proposal(org_id,i as u64 * 100,OWNER);
-				assert_ok!(IdavollModule::base_create_proposal(org_id,proposal.clone()));
-				let proposal_id = IdavollModule::make_proposal_id(&proposal.clone());
+				let proposal_id = IdavollModule::base_create_proposal(i,proposal.clone()).unwrap();
 				assert_eq!(IdavollModule::get_proposal_by_id(proposal_id),Ok(proposal.clone()));
 				assert_eq!(proposal.detail.creator(),OWNER.clone());
 				assert_eq!(set_block_number(i as u64),get_block_number());
@@ -622,68 +1247,83 @@ mod test {
 
 			// vote on decision 1
 			for i in 0..10 {
-				proposal.detail.vote(i,7,true);
+				proposal.detail.vote(i,7,Vote::Aye,Conviction::None);
 			}
 			for i in 10..15 {
-				proposal.detail.vote(i,5,false);
+				proposal.detail.vote(i,5,Vote::Nay,Conviction::None);
 			}
-			assert_eq!(proposal.detail.summary(),(70,25));
+			assert_eq!(proposal.detail.summary(),(70,25,0));
 			assert_eq!(proposal.detail.is_passed(100), false);
 
 			// vote on decision 2
 			proposal.detail.votes = BTreeMap::new();
 			for i in 0..10 {
-				proposal.detail.vote(i,7,true);
+				proposal.detail.vote(i,7,Vote::Aye,Conviction::None);
 			}
 			for i in 10..13 {
-				proposal.detail.vote(i,1,false);
+				proposal.detail.vote(i,1,Vote::Nay,Conviction::None);
 			}
-			assert_eq!(proposal.detail.summary(),(70,3));
+			assert_eq!(proposal.detail.summary(),(70,3,0));
 			assert_eq!(proposal.detail.is_passed(100), true);
 
 			// vote on decision 3
 			proposal.detail.votes = BTreeMap::new();
 			for i in 0..10 {
-				proposal.detail.vote(i,8,true);
+				proposal.detail.vote(i,8,Vote::Aye,Conviction::None);
 			}
 			for i in 10..15 {
-				proposal.detail.vote(i,1,false);
+				proposal.detail.vote(i,1,Vote::Nay,Conviction::None);
 			}
-			assert_eq!(proposal.detail.summary(),(80,5));
+			assert_eq!(proposal.detail.summary(),(80,5,0));
 			assert_eq!(proposal.detail.is_passed(100), true);
 
 			// vote on decision 4
 			proposal.detail.votes = BTreeMap::new();
 			for i in 0..10 {
-				proposal.detail.vote(i,6,true);
+				proposal.detail.vote(i,6,Vote::Aye,Conviction::None);
 			}
 			for i in 10..12 {
-				proposal.detail.vote(i,1,false);
+				proposal.detail.vote(i,1,Vote::Nay,Conviction::None);
 			}
-			assert_eq!(proposal.detail.summary(),(60,2));
+			assert_eq!(proposal.detail.summary(),(60,2,0));
 			assert_eq!(proposal.detail.is_passed(100), false);
 
 			// vote on decision 5
 			proposal.detail.votes = BTreeMap::new();
 			for i in 0..10 {
-				proposal.detail.vote(i,7,true);
+				proposal.detail.vote(i,7,Vote::Aye,Conviction::None);
 			}
 			for i in 10..16 {
-				proposal.detail.vote(i,1,false);
+				proposal.detail.vote(i,1,Vote::Nay,Conviction::None);
 			}
-			assert_eq!(proposal.detail.summary(),(70,6));
+			assert_eq!(proposal.detail.summary(),(70,6,0));
 			assert_eq!(proposal.detail.is_passed(100), false);
 
 			// vote on decision 6
 			proposal.detail.votes = BTreeMap::new();
 			for i in 0..10 {
-				proposal.detail.vote(i,6,true);
+				proposal.detail.vote(i,6,Vote::Aye,Conviction::None);
 			}
 			for i in 10..15 {
-				proposal.detail.vote(i,1,false);
+				proposal.detail.vote(i,1,Vote::Nay,Conviction::None);
 			}
-			assert_eq!(proposal.detail.summary(),(60,5));
+			assert_eq!(proposal.detail.summary(),(60,5,0));
 			assert_eq!(proposal.detail.is_passed(100), false);
+
+			// vote on decision 7, no more than 3% 'abstain' votes
+			proposal.detail.sub_param = OrgRuleParam::new(60,5,3);
+			proposal.detail.votes = BTreeMap::new();
+			for i in 0..10 {
+				proposal.detail.vote(i,7,Vote::Aye,Conviction::None);
+			}
+			for i in 10..14 {
+				proposal.detail.vote(i,1,Vote::Abstain,Conviction::None);
+			}
+			assert_eq!(proposal.detail.summary(),(70,0,4));
+			assert_eq!(proposal.detail.is_passed(100), false);
+			proposal.detail.votes.remove(&13);
+			assert_eq!(proposal.detail.summary(),(70,0,3));
+			assert_eq!(proposal.detail.is_passed(100), true);
 		});
 	}
 
@@ -740,6 +1380,111 @@ mod test {
 			assert_eq!(param.inherit_valid(sub.clone()),true);
 			sub.min_affirmative = 70;sub.max_dissenting = 10;sub.abstention = 4;
 			assert_eq!(param.inherit_valid(sub.clone()),false);
+
+			// the zero limits are unlimited, they can't remove the limits of the organization
+			sub.min_affirmative = 70;sub.max_dissenting = 0;sub.abstention = 3;
+			assert_eq!(param.inherit_valid(sub.clone()),false);
+			sub.min_affirmative = 70;sub.max_dissenting = 10;sub.abstention = 0;
+			assert_eq!(param.inherit_valid(sub.clone()),false);
+			param.max_dissenting = 0;param.abstention = 0;
+			sub.min_affirmative = 70;sub.max_dissenting = 50;sub.abstention = 0;
+			assert_eq!(param.inherit_valid(sub.clone()),true);
+			sub.quorum = 0;sub.approval = ApprovalMode::Participants;
+			assert_eq!(param.with_quorum(10,ApprovalMode::Participants).inherit_valid(sub.clone()),false);
+		});
+	}
+
+	#[test]
+	fn base_rule_quorum_should_work() {
+		new_test_ext().execute_with(|| {
+			// at least 40% of the supply take part, and more than 66% of the participants agree
+			let param = OrgRuleParam::new(66,0,0).with_quorum(40,ApprovalMode::Participants);
+			assert_eq!(param.is_passed(39 as u64, 0, 0, 100), false);
+			assert_eq!(param.is_passed(40 as u64, 0, 0, 100), true);
+			assert_eq!(param.is_passed(27 as u64, 13, 0, 100), true);
+			assert_eq!(param.is_passed(26 as u64, 14, 0, 100), false);
+			assert_eq!(param.is_passed(27 as u64, 3, 10, 100), true);
+			assert_eq!(param.is_passed(70 as u64, 30, 0, 100), true);
+			assert_eq!(param.is_passed(66 as u64, 34, 0, 100), false);
+
+			// the quorum with the thresholds of the supply
+			let param = OrgRuleParam::new(30,0,0).with_quorum(50,ApprovalMode::Supply);
+			assert_eq!(param.is_passed(31 as u64, 18, 0, 100), false);
+			assert_eq!(param.is_passed(31 as u64, 19, 0, 100), true);
+			assert_eq!(param.is_passed(30 as u64, 30, 0, 100), false);
+			assert_eq!(param.is_valid(), true);
+			assert_eq!(OrgRuleParam::<u64>::new(30,0,0).with_quorum(101,ApprovalMode::Supply).is_valid(), false);
+
+			// sub param
+			assert_eq!(param.inherit_valid(param.clone()),true);
+			assert_eq!(param.inherit_valid(OrgRuleParam::new(30,0,0).with_quorum(60,ApprovalMode::Supply)),true);
+			assert_eq!(param.inherit_valid(OrgRuleParam::new(30,0,0).with_quorum(40,ApprovalMode::Supply)),false);
+			assert_eq!(param.inherit_valid(OrgRuleParam::new(30,0,0).with_quorum(50,ApprovalMode::Participants)),false);
+			assert_eq!(param.inherit_valid(OrgRuleParam::new(30,0,0)),false);
+		});
+	}
+
+	#[test]
+	fn base_rule_kind_should_work() {
+		new_test_ext().execute_with(|| {
+			// the threshold kind was passed by the param
+			let param = OrgRuleParam::new(60,5,0);
+			assert_eq!(RuleKind::Threshold.is_passed(&param, (61 as u64,5,0), (61 as u64,5,0), 100), true);
+			assert_eq!(RuleKind::Threshold.is_passed(&param, (60 as u64,5,0), (60 as u64,5,0), 100), false);
+			assert_eq!(RuleKind::Threshold.is_passed(&param, (61 as u64,6,0), (61 as u64,6,0), 100), false);
+
+			// the other kinds ignore the param
+			assert_eq!(RuleKind::SimpleMajority.is_passed(&param, (51 as u64,49,0), (51 as u64,49,0), 100), true);
+			assert_eq!(RuleKind::SimpleMajority.is_passed(&param, (50 as u64,0,0), (50 as u64,0,0), 100), false);
+
+			assert_eq!(RuleKind::SuperMajority.is_passed(&param, (67 as u64,33,0), (67 as u64,33,0), 100), true);
+			assert_eq!(RuleKind::SuperMajority.is_passed(&param, (66 as u64,0,0), (66 as u64,0,0), 100), false);
+			assert_eq!(RuleKind::SuperMajority.is_passed(&param, (0 as u64,0,0), (0 as u64,0,0), 0), false);
+
+			assert_eq!(RuleKind::QuorumMajority.is_passed(&param, (30 as u64,20,1), (30 as u64,20,1), 100), true);
+			assert_eq!(RuleKind::QuorumMajority.is_passed(&param, (30 as u64,20,0), (30 as u64,20,0), 100), false);
+			assert_eq!(RuleKind::QuorumMajority.is_passed(&param, (30 as u64,30,10), (30 as u64,30,10), 100), false);
+
+			assert_eq!(RuleKind::Unanimous.is_passed(&param, (100 as u64,0,0), (100 as u64,0,0), 100), true);
+			assert_eq!(RuleKind::Unanimous.is_passed(&param, (99 as u64,0,0), (99 as u64,0,0), 100), false);
+
+			// the conviction weighted votes can't reach the thresholds of the total
+			assert_eq!(RuleKind::Unanimous.is_passed(&param, (100 as u64,0,0), (25 as u64,0,0), 100), false);
+			assert_eq!(RuleKind::Threshold.is_passed(&param, (80 as u64,0,0), (20 as u64,0,0), 100), false);
+			assert_eq!(RuleKind::QuorumMajority.is_passed(&param, (30 as u64,0,0), (10 as u64,0,0), 100), false);
+			// but they count against the other votes
+			assert_eq!(RuleKind::QuorumMajority.is_passed(&param, (90 as u64,60,0), (30 as u64,60,0), 100), true);
+			let param = OrgRuleParam::new(60,0,0).with_quorum(30,ApprovalMode::Participants);
+			assert_eq!(RuleKind::Threshold.is_passed(&param, (90 as u64,20,0), (30 as u64,20,0), 100), true);
+			assert_eq!(RuleKind::Threshold.is_passed(&param, (90 as u64,20,0), (9 as u64,20,0), 100), false);
+		});
+	}
+
+	#[test]
+	fn base_close_passed_proposal_should_work() {
+		new_test_ext().execute_with(|| {
+			let mut org = create_org();
+			let asset_id = IdavollModule::create_new_token(OWNER.clone(),100);
+			org.set_asset_id(asset_id.clone());
+			assert_ok!(IdavollModule::storage_new_organization(org.clone(),vec![OWNER,1,2,3]));
+			let org_id = IdavollModule::counter_2_orgid(0);
+			assert_ok!(IdavollModule::reserve_to_vault(org_id.clone(),A.clone(),30));
+
+			// the proposal was passed but not closed by the votes
+			let mut proposal = create_proposal3(org_id,make_transfer_proposal(10));
+			proposal.detail.vote(OWNER,80,Vote::Aye,Conviction::None);
+			assert_ok!(IdavollAsset::lock(asset_id,&OWNER,80));
+			assert_ok!(IdavollAsset::vault_lock_asset(org_id.clone(),OWNER,1));
+			let proposal_id = IdavollModule::base_create_proposal(0,proposal.clone()).unwrap();
+
+			let call_weight = Call::IdavollModule(crate::Call::vault_transfer(RECEIVER.clone(),10)).get_dispatch_info().weight;
+			let info = IdavollModule::close_proposal(Origin::signed(A),proposal_id,call_weight).unwrap();
+			assert_eq!(info.actual_weight,Some(<() as WeightInfo>::close_proposal() + call_weight));
+			assert_noop!(IdavollModule::get_proposal_by_id(proposal_id),Error::<Test>::ProposalNotFound);
+			assert_eq!(IdavollModule::get_local_balance(org_id.clone()),Ok(20));
+			assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+			assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER),100);
+			assert_eq!(IdvBalances::free_balance(OWNER.clone()),300000);
 		});
 	}
 
@@ -747,7 +1492,7 @@ mod test {
 	fn base_dispatch_01_should_work() {
 		new_test_ext().execute_with(|| {
 			let proposal = create_proposal2(make_transfer_fail_proposal(10));
-			assert_ok!(IdavollModule::base_create_proposal(ORGID.clone(),proposal.clone()));
+			let proposal_id = IdavollModule::base_create_proposal(0,proposal.clone()).unwrap();
 			assert_ok!(IdavollModule::reserve_to_vault(ORGID.clone(),A.clone(),30));
 			assert_eq!(IdavollModule::get_local_balance(ORGID),Ok(30));
 
@@ -756,7 +1501,6 @@ mod test {
 			assert_noop!(IdavollModule::get_local_balance(RECEIVER),IdavollAssetError::UnknownOwnerID);
 
 			// transfer the asset from organization id(it is fail),cause it's not transfer by direct
-			let proposal_id = IdavollModule::make_proposal_id(&proposal.clone());
 			assert_ok!(IdavollModule::base_call_dispatch(proposal_id,proposal.clone()));
 			assert_noop!(IdavollModule::get_local_balance(RECEIVER),IdavollAssetError::UnknownOwnerID);
 			assert_eq!(IdvBalances::free_balance(ORGID.clone()),0);
@@ -768,14 +1512,13 @@ mod test {
 	fn base_dispatch_02_should_work() {
 		new_test_ext().execute_with(|| {
 			let proposal = create_proposal2(make_transfer_proposal(10));
-			assert_ok!(IdavollModule::base_create_proposal(ORGID.clone(),proposal.clone()));
+			let proposal_id = IdavollModule::base_create_proposal(0,proposal.clone()).unwrap();
 			assert_ok!(IdavollModule::reserve_to_vault(ORGID.clone(),A.clone(),30));
 			assert_eq!(IdavollModule::get_local_balance(ORGID.clone()),Ok(30));
 			assert_eq!(IdvBalances::free_balance(ORGID.clone()),0);
 
 
 			// transfer the asset from organization id(it is success)
-			let proposal_id = IdavollModule::make_proposal_id(&proposal.clone());
 			assert_ok!(IdavollModule::base_call_dispatch(proposal_id,proposal.clone()));
 			assert_eq!(IdavollModule::get_local_balance(ORGID.clone()),Ok(20));
 			assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
@@ -792,13 +1535,12 @@ mod test {
 			let mut sum = 0;
 			for i in 10..100 {
 				let proposal = create_proposal3(i as u128,make_transfer_proposal(i));
-				assert_ok!(IdavollModule::base_create_proposal(i as u128,proposal.clone()));
+				let proposal_id = IdavollModule::base_create_proposal(i as u32,proposal.clone()).unwrap();
 				assert_ok!(IdavollModule::reserve_to_vault(i as u128,A.clone(),i));
 				assert_eq!(IdavollModule::get_local_balance(i as u128),Ok(i));
 
 				sum += i;
 				// transfer the asset from organization id(it is success)
-				let proposal_id = IdavollModule::make_proposal_id(&proposal.clone());
 				assert_ok!(IdavollModule::base_call_dispatch(proposal_id,proposal.clone()));
 				assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),sum);
 				assert_noop!(IdavollModule::get_local_balance(RECEIVER),IdavollAssetError::UnknownOwnerID);
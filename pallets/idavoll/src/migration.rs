@@ -0,0 +1,145 @@
+/*
+ * Copyright 2021 Idavoll Network
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+
+/// Storage migrations of the pallet
+///
+///
+use frame_support::{
+    StorageValue, StorageMap, IterableStorageMap, weights::{GetDispatchInfo, Weight}, traits::Get,
+    storage::migration::StorageIterator,
+};
+use crate::{Module, Trait, OrgInfoOf, OrgInfos, OrgCounter, OrgCount, MetaOrganization, StorageVersion, Releases, Vote, Conviction};
+use crate::organization::{OrgInfo, AssetInfo, AdmissionPolicy, VotingMode, Proposal, ProposalDetail};
+use crate::rules::{OrgRuleParam, RuleKind};
+use sp_runtime::traits::{One, Saturating, Zero};
+use idavoll_asset::{token::BaseToken, finance::BaseFinance};
+use codec::{Compact, Decode, Encode};
+use sp_std::{cell::Cell, prelude::Vec, collections::btree_map::BTreeMap};
+
+/// The layout of `OrgInfo` before the members were moved into the `Members` storage,
+/// the `param` was encoded as `(min_affirmative, max_dissenting, abstention)`.
+#[derive(Encode, Decode)]
+struct OldOrgInfo<AccountId, AssetId: Clone + Default> {
+    members: Vec<AccountId>,
+    param: (u32, u32, u32),
+    asset: AssetInfo<AssetId>,
+}
+
+type OldOrgInfoOf<T> = OldOrgInfo<<T as frame_system::Trait>::AccountId, <T as Trait>::TokenId>;
+
+/// The layout of `Proposal` keyed by the hash of the proposal, the `call` was a single encoded
+/// call and the votes were `(coins, in agree or against)`.
+#[derive(Encode, Decode)]
+struct OldProposal<AccountId: Ord, Balance, BlockNumber> {
+    org: AccountId,
+    call: Vec<u8>,
+    votes: BTreeMap<AccountId, (Balance, bool)>,
+    creator: AccountId,
+    end_dt: BlockNumber,
+    sub_param: (u32, u32, u32),
+}
+
+type OldProposalOf<T> = OldProposal<<T as frame_system::Trait>::AccountId, <T as Trait>::Balance,
+    <T as frame_system::Trait>::BlockNumber>;
+
+/// Migrate the storage of the first release: move the members of every organization from
+/// `OrgInfo` into the `Members` storage, fill the new fields of the organizations with the
+/// defaults and store the open proposals with the sequential proposal ids. The expired proposals
+/// are closed in the next blocks, and the proposals which can't be stored release their locks.
+pub fn migrate_to_v2<T: Trait>() -> Weight {
+    if StorageVersion::get() != Releases::V1 {
+        return 0;
+    }
+    let reads_writes = Cell::new(1 as Weight);
+    OrgInfos::<T>::translate::<OldOrgInfoOf<T>, _>(|oid, old| {
+        old.members.iter().for_each(|member| {
+            // the organization was exists, adding members never fails
+            let _ = Module::<T>::base_add_member_by_orgid(oid.clone(), member.clone());
+        });
+        reads_writes.set(reads_writes.get().saturating_add(old.members.len() as Weight * 2 + 1));
+        let (min_affirmative, max_dissenting, abstention) = old.param;
+        let info: OrgInfoOf<T> = OrgInfo {
+            param: OrgRuleParam::new(min_affirmative, max_dissenting, abstention),
+            rule: RuleKind::default(),
+            admission: AdmissionPolicy::default(),
+            asset: old.asset,
+            mode: VotingMode::default(),
+            guardian: None,
+        };
+        Some(info)
+    });
+
+    // the organization number and the token of every organization, keyed by the organization id
+    let counter = OrgCounter::get();
+    let orgs: BTreeMap<T::AccountId, (OrgCount, T::TokenId)> = (0..counter).filter_map(|id| {
+        let oid = Module::<T>::counter_2_orgid(id);
+        OrgInfos::<T>::get(&oid).map(|info| (oid, (id, info.get_asset_id())))
+    }).collect();
+    reads_writes.set(reads_writes.get().saturating_add(counter as Weight));
+
+    // the proposals are re-keyed, so they are drained before storing them again
+    let old_proposals: Vec<(Vec<u8>, OldProposalOf<T>)> =
+        StorageIterator::<OldProposalOf<T>>::new(b"IdavollModule", b"Proposals").drain().collect();
+    // the expired proposals are closed in the next blocks
+    let next = frame_system::Module::<T>::block_number().saturating_add(One::one());
+    for (_, old) in old_proposals.into_iter() {
+        reads_writes.set(reads_writes.get().saturating_add(6 + old.votes.len() as Weight));
+        // the tokens of the proposal can't be found without the organization
+        let (id, aid) = match orgs.get(&old.org) {
+            Some(org) => *org,
+            None => continue,
+        };
+        // the single call becomes the only call of the proposal
+        let mut call = Compact(1u32).encode();
+        call.extend(old.call);
+        let weight = Module::<T>::decode_calls(&call).unwrap_or_default().iter()
+            .fold(0 as Weight, |acc, c| acc.saturating_add(c.get_dispatch_info().weight));
+        let (min_affirmative, max_dissenting, abstention) = old.sub_param;
+        let mut detail = ProposalDetail::new(old.creator.clone(), old.end_dt.max(next),
+            OrgRuleParam::new(min_affirmative, max_dissenting, abstention));
+        detail.votes = old.votes.iter().map(|(voter, (value, vote_for))| {
+            let vote = if *vote_for { Vote::Aye } else { Vote::Nay };
+            (voter.clone(), (*value, vote, Conviction::None))
+        }).collect();
+        let proposal = Proposal::new(old.org.clone(), call, detail).with_weight(weight);
+        if Module::<T>::base_create_proposal(id, proposal).is_err() {
+            // release the locked tokens and the stake of the proposal which can't be stored
+            old.votes.iter().filter(|(_, (value, _))| !value.is_zero()).for_each(|(voter, (value, _))| {
+                let _ = T::TokenHandler::unlock(aid, voter, *value);
+            });
+            let _ = T::Finance::unlock_balance(old.org, old.creator, T::InherentStakeProposal::get());
+        }
+    }
+    StorageVersion::put(Releases::V2);
+    T::DbWeight::get().reads_writes(reads_writes.get(), reads_writes.get())
+}
+
+/// Set the organization of the `MetaOrgNumber` as the meta organization if the chain has no
+/// meta organization, the chain upgraded from the first release has no path to the Root origin
+/// without it.
+pub fn set_meta_organization<T: Trait>() -> Weight {
+    if MetaOrganization::<T>::get().is_some() {
+        return T::DbWeight::get().reads(1);
+    }
+    match T::MetaOrgNumber::get() {
+        Some(id) if id < OrgCounter::get() => {
+            MetaOrganization::<T>::put(Module::<T>::counter_2_orgid(id));
+            T::DbWeight::get().reads_writes(2, 1)
+        }
+        _ => T::DbWeight::get().reads(2),
+    }
+}
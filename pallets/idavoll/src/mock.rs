@@ -21,22 +21,24 @@ use crate as idavoll;
 use frame_support::{
 	codec::{Encode},
 	impl_outer_origin,impl_outer_dispatch,
-	parameter_types, weights::Weight};
+	parameter_types, weights::{GetDispatchInfo, Weight}};
 use sp_core::H256;
 use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header,ModuleId};
 use pallet_balances;
 use frame_system::RawOrigin;
-use sp_std::{prelude::Vec, boxed::Box};
+use sp_std::prelude::Vec;
 
 
 impl_outer_origin! {
-		pub enum Origin for Test where system = frame_system {}
+		pub enum Origin for Test where system = frame_system {
+			idavoll<T>,
+		}
 	}
 impl_outer_dispatch! {
 		pub enum Call for Test where origin: Origin {
 			frame_system::System,
-			// pallet_balances::IdvBalances,
 			idavoll::IdavollModule,
+			pallet_balances::IdvBalances,
         }
     }
 
@@ -59,6 +61,8 @@ parameter_types! {
 		pub const MaximumBlockLength: u32 = 2 * 1024;
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
 		pub const IdvAssetModuleId: ModuleId = ModuleId(*b"py/asset");
+		pub const MaxUnlocksPerBlock: u32 = 50;
+		pub const MaxUnlockDelay: u32 = 10;
 		pub const IdavollModuleId: ModuleId = ModuleId(*b"py/idvol");
 	}
 impl frame_system::Trait for Test {
@@ -92,6 +96,17 @@ impl frame_system::Trait for Test {
 parameter_types! {
 	pub const ExistentialDeposit: u64 = 10;
 	pub const InherentStakeProposal: u64 = 1;
+	pub const MaxExpiredPerBlock: u32 = 2;
+	pub const VoteLockingPeriod: u64 = 10;
+	pub const MaxEnactmentsPerBlock: u32 = 2;
+	pub const MaxEnactmentWeight: Weight = 250_000;
+	pub const MaxTitleLength: u32 = 16;
+	pub const MaxContentLength: u32 = 64;
+	pub const MaxProposalCalls: u32 = 4;
+	pub const MaxProposalWeight: Weight = 400_000_000_000;
+	pub const MetaOrgNumber: Option<OrgCount> = Some(0);
+	pub const MaxDelegatorsPerDelegate: u32 = 2;
+	pub const MaxMembers: u32 = 100;
     }
 impl pallet_balances::Trait for Test {
 	type Balance = u64;
@@ -109,23 +124,40 @@ impl idavoll_asset::Trait for Test {
 	type AssetId = u32;
 	type Currency = IdvBalances;
 	type ModuleId = IdvAssetModuleId;
+	type MaxUnlocksPerBlock = MaxUnlocksPerBlock;
+	type MaxUnlockDelay = MaxUnlockDelay;
 }
 
 pub type IdavollModule = Module<Test>;
 type IdavallCall = idavoll::Call<Test>;
 impl Trait for Test {
 	type Event = ();
+	type Origin = Origin;
 	type Call = Call;
 	type Balance = u64;
 	type TokenId = u32;
 	type ModuleId = IdavollModuleId;
 	type TokenHandler = IdavollAsset;
 	type Finance = IdavollAsset;
+	type RuleHandler = DefaultRule<Test>;
 	type InherentStakeProposal = InherentStakeProposal;
+	type MaxExpiredPerBlock = MaxExpiredPerBlock;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type MaxEnactmentsPerBlock = MaxEnactmentsPerBlock;
+	type MaxEnactmentWeight = MaxEnactmentWeight;
+	type MaxTitleLength = MaxTitleLength;
+	type MaxContentLength = MaxContentLength;
+	type MaxProposalCalls = MaxProposalCalls;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxDelegatorsPerDelegate = MaxDelegatorsPerDelegate;
+	type MaxMembers = MaxMembers;
+	type VetoOrigin = frame_system::EnsureRoot<u128>;
+	type MetaOrgNumber = MetaOrgNumber;
+	type RootOrigin = idavoll::EnsureMetaOrg<Test>;
 	type WeightInfo = ();
 }
 
-pub fn new_test_ext() -> sp_io::TestExternalities {
+fn genesis_storage() -> sp_runtime::Storage {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	let genesis = pallet_balances::GenesisConfig::<Test> {
 		balances: vec![
@@ -135,6 +167,20 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		],
 	};
 	genesis.assimilate_storage(&mut t).unwrap();
+	t
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	genesis_storage().into()
+}
+
+/// the test externalities with the organizations created at genesis
+pub fn new_test_ext_with_organizations(organizations: Vec<(u128, u64, OrgInfoOf<Test>, Vec<(u128, u64)>)>) -> sp_io::TestExternalities {
+	let mut t = genesis_storage();
+	let genesis = idavoll::GenesisConfig::<Test> {
+		organizations,
+	};
+	genesis.assimilate_storage(&mut t).unwrap();
 	t.into()
 }
 pub fn set_block_number(n: <Test as frame_system::Trait>::BlockNumber) -> <Test as frame_system::Trait>::BlockNumber {
@@ -144,16 +190,15 @@ pub fn set_block_number(n: <Test as frame_system::Trait>::BlockNumber) -> <Test
 pub fn get_block_number() -> <Test as frame_system::Trait>::BlockNumber {
 	System::block_number()
 }
-pub fn call_to_vec(call: Box<<Test as Trait>::Call>) -> Vec<u8> {
-	call.encode()
+pub fn call_to_vec(calls: Vec<<Test as Trait>::Call>) -> Vec<u8> {
+	calls.encode()
 }
-pub fn make_transfer_proposal(value: u64) -> Box<Call> {
-	Box::new(Call::IdavollModule(IdavallCall::vault_transfer(RECEIVER.clone(),value)))
+pub fn make_transfer_proposal(value: u64) -> Vec<Call> {
+	vec![Call::IdavollModule(IdavallCall::vault_transfer(RECEIVER.clone(),value))]
 }
 
-pub fn create_org(members: Vec<<Test as frame_system::Trait>::AccountId>) -> OrgInfoOf<Test> {
+pub fn create_org() -> OrgInfoOf<Test> {
 	let mut org = OrgInfo::new();
-	org.members = members.clone();
 	org.param = get_rule();
 	org.clone()
 }
@@ -167,13 +212,27 @@ pub fn create_proposal_without_storage(id: u128,expire: u64,call: Vec<u8>) -> Pr
 		org:    id.clone(),
 		call: 	call.clone(),
 		detail: ProposalDetail::new(OWNER.clone(),cur,get_rule()),
+		title: vec![],
+		content: vec![],
+		weight: proposal_weight(&call),
 	}
 }
 
+/// the total dispatch weight of the encoded calls
+pub fn proposal_weight(call: &[u8]) -> Weight {
+	IdavollModule::decode_calls(call).unwrap_or_default().iter()
+		.fold(0,|acc,c| acc.saturating_add(c.get_dispatch_info().weight))
+}
+
+/// the origin of the proposals passed by the organization
+pub fn org_origin(oid: u128) -> Origin {
+	idavoll::RawOrigin::Organization(oid,Default::default()).into()
+}
+
 pub fn create_new_organization(creator: u128,total: u64) -> u128 {
-	let info = create_org(vec![]);
+	let info = create_org();
 	let c = IdavollModule::counter_of();
-	match IdavollModule::create_organization(RawOrigin::Signed(creator).into(),total,info) {
+	match IdavollModule::create_organization(RawOrigin::Signed(creator).into(),total,info,vec![]) {
 		Ok(_val) => {
 			IdavollModule::counter_2_orgid(c)
 		},
@@ -18,24 +18,136 @@
 ///
 ///
 
-use frame_support::{ensure, dispatch::{self, Parameter}};
-use crate::rules::{OrgRuleParam};
+use frame_support::{ensure, StorageMap, IterableStorageDoubleMap, dispatch::{self, Parameter, IsSubType}, weights::{GetDispatchInfo, Weight}};
+use crate::rules::{OrgRuleParam, RuleKind, BaseRule};
 use crate::{
-    ProposalOf,ProposalIdOf,Error,
+    ProposalOf,ProposalId,Error,OrgInfoOf,
     Module, Trait, OrgRuleParamOf,
-    BalanceOf};
+    BalanceOf,Proposals,OrgInfos,OrgProposals,Members,MemberCount,EnactmentDelay,CallWhitelist,RawEvent};
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use codec::{Decode, Encode};
-use sp_runtime::{RuntimeDebug, traits::{Hash as FrameHash,AtLeast32BitUnsigned,Member, Zero}, DispatchResult};
-use sp_std::{cmp::PartialOrd,prelude::Vec, boxed::Box,collections::btree_map::BTreeMap};
+use sp_runtime::{RuntimeDebug, traits::{AtLeast32BitUnsigned,Member, One, Zero, StaticLookup}, DispatchResult};
+use sp_std::{cmp::PartialOrd,prelude::Vec,collections::btree_map::BTreeMap};
 use idavoll_asset::{token::BaseToken,finance::BaseFinance};
 use frame_support::sp_runtime::DispatchError;
 use frame_support::traits::Get;
 
 // pub type OrganizationId = u64;
 
+/// The stance of a voter on a proposal.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Vote {
+    /// vote in agree with the proposal
+    Aye,
+    /// vote against the proposal
+    Nay,
+    /// take part in the vote without agree or against, it was limited by the `abstention`
+    /// threshold of the rule
+    Abstain,
+}
+
+impl Default for Vote {
+    fn default() -> Self {
+        Vote::Aye
+    }
+}
+
+/// The conviction of the vote, the voter keeps the tokens locked for some vote locking periods
+/// after the proposal ended, and the vote weight is multiplied in exchange. The multiplied weight
+/// only counts against the other votes, the turnout and the thresholds of the total supply are
+/// counted by the locked tokens.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Conviction {
+    /// 1x vote weight, the tokens are unlocked when the proposal ended
+    None,
+    /// 2x vote weight, the tokens are locked for 1 period after the proposal ended
+    Locked2x,
+    /// 3x vote weight, the tokens are locked for 2 periods after the proposal ended
+    Locked3x,
+    /// 4x vote weight, the tokens are locked for 4 periods after the proposal ended
+    Locked4x,
+}
+
+impl Default for Conviction {
+    fn default() -> Self {
+        Conviction::None
+    }
+}
+
+impl Conviction {
+    /// The multiplier of the vote weight
+    pub fn multiplier(&self) -> u32 {
+        match self {
+            Conviction::None => 1,
+            Conviction::Locked2x => 2,
+            Conviction::Locked3x => 3,
+            Conviction::Locked4x => 4,
+        }
+    }
+    /// The vote locking periods after the proposal ended
+    pub fn lock_periods(&self) -> u32 {
+        match self {
+            Conviction::None => 0,
+            Conviction::Locked2x => 1,
+            Conviction::Locked3x => 2,
+            Conviction::Locked4x => 4,
+        }
+    }
+    /// The vote weight of the `value` locked tokens
+    pub fn votes<Balance: AtLeast32BitUnsigned>(&self, value: Balance) -> Balance {
+        value.saturating_mul(self.multiplier().into())
+    }
+}
+
+/// The way the locked tokens of a vote are counted as the vote units.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VotingMode {
+    /// N locked tokens take N vote units
+    Token,
+    /// N locked tokens take √N vote units
+    Quadratic,
+    /// every member takes 1 vote unit, no tokens are locked
+    Membership,
+}
+
+impl Default for VotingMode {
+    fn default() -> Self {
+        VotingMode::Token
+    }
+}
+
+impl VotingMode {
+    /// The vote units of the `value` locked tokens
+    pub fn units<Balance: AtLeast32BitUnsigned>(&self, value: Balance) -> Balance {
+        match self {
+            VotingMode::Token => value,
+            VotingMode::Quadratic => value.integer_sqrt(),
+            VotingMode::Membership => One::one(),
+        }
+    }
+    /// The maximum vote units that the `count` members with the `total` tokens can take, the
+    /// quadratic units are the square roots sum of the token `balances` of the members, so the
+    /// members without tokens take nothing. The thresholds are computed against it.
+    pub fn total_units<Balance, I>(&self, total: Balance, count: u32, balances: I) -> Balance
+    where
+        Balance: AtLeast32BitUnsigned,
+        I: Iterator<Item = Balance>,
+    {
+        match self {
+            VotingMode::Token => total,
+            VotingMode::Quadratic => balances.fold(Zero::zero(), |units: Balance, balance| {
+                units.saturating_add(balance.integer_sqrt())
+            }),
+            VotingMode::Membership => count.into(),
+        }
+    }
+}
+
 /// this is the free proposal,every one in the organization can create
 /// the proposal for pay a little fee, it not staking any asset to do this.
 #[derive(Eq, PartialEq, RuntimeDebug, Encode, Decode, Clone, Default)]
@@ -46,8 +158,8 @@ pub struct ProposalDetail<AccountId, Balance, BlockNumber>
         Balance: Member + Parameter + AtLeast32BitUnsigned + Copy + Default,
         BlockNumber: Eq + PartialOrd + Clone,
 {
-    /// A map of voter => (coins, in agree or against)
-    pub votes: BTreeMap<AccountId, (Balance, bool)>,
+    /// A map of voter => (coins, aye, nay or abstain, conviction)
+    pub votes: BTreeMap<AccountId, (Balance, Vote, Conviction)>,
     /// the creator of the proposal
     pub creator: AccountId,
     /// the end datetime(block number) of the proposal,it set by created.
@@ -55,6 +167,8 @@ pub struct ProposalDetail<AccountId, Balance, BlockNumber>
     /// the user-default param for the vote rule in the proposal.
     /// it must be in range of the organization's param
     pub sub_param: OrgRuleParam<Balance>,
+    /// the voting mode of the organization when the proposal was created
+    pub mode: VotingMode,
 }
 
 impl<AccountId: Ord + Clone,
@@ -63,42 +177,56 @@ impl<AccountId: Ord + Clone,
     > ProposalDetail<AccountId, Balance, BlockNumber> {
     pub fn new(who: AccountId,end: BlockNumber,subparam: OrgRuleParam<Balance>) -> Self {
         ProposalDetail{
-            votes: BTreeMap::<AccountId, (Balance, bool)>::new(),
+            votes: BTreeMap::<AccountId, (Balance, Vote, Conviction)>::new(),
             creator: who,
             end_dt: end,
             sub_param: subparam,
+            mode: VotingMode::default(),
         }
     }
-    pub fn vote(&mut self,voter: AccountId,value: Balance,yesorno: bool) {
-        if let Some(val) = self.votes.get_mut(&voter) {
-            if val.1 == yesorno {
-                *val = (value.saturating_add(val.0),yesorno);
-            } else {
-                *val = (value.saturating_add(val.0),val.1);
-            }
-        } else {
-            self.votes.insert(voter,(value,yesorno));
-        }
+    /// Count the votes of the proposal by the voting `mode`
+    pub fn with_mode(mut self, mode: VotingMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// The vote replaces the previous vote of the `voter`, return the replaced vote.
+    pub fn vote(&mut self,voter: AccountId,value: Balance,stance: Vote,conviction: Conviction) -> Option<(Balance,Vote,Conviction)> {
+        self.votes.insert(voter,(value,stance,conviction))
+    }
+    /// Remove the vote of the `voter`, return the removed vote.
+    pub fn remove_vote(&mut self,voter: &AccountId) -> Option<(Balance,Vote,Conviction)> {
+        self.votes.remove(voter)
     }
-    pub fn summary(&self) -> (Balance,Balance) {
-        let (mut yes_balance,mut no_balance) = (Balance::default(),Balance::default());
+    /// Sum the vote units weighted by the conviction of the proposal, return the (aye, nay, abstain)
+    /// vote units.
+    pub fn summary(&self) -> (Balance,Balance,Balance) {
+        self.sum_votes(|units, conviction| conviction.votes(units))
+    }
+    /// Sum the vote units without the conviction, return the (aye, nay, abstain) vote units.
+    /// The conviction can't create the units beyond the total, so the thresholds of the total
+    /// and the turnout are counted by them.
+    pub fn tokens(&self) -> (Balance,Balance,Balance) {
+        self.sum_votes(|units, _| units)
+    }
+    fn sum_votes<F: Fn(Balance, &Conviction) -> Balance>(&self, weigh: F) -> (Balance,Balance,Balance) {
+        let (mut yes_balance,mut no_balance,mut nu_balance) =
+            (Balance::default(),Balance::default(),Balance::default());
         self.votes.iter().for_each(|val|{
-            if val.1.1 {
-                yes_balance = yes_balance.saturating_add(val.1.0);
-            } else {
-                no_balance = no_balance.saturating_add(val.1.0);
+            let weight = weigh(self.mode.units(val.1.0), &val.1.2);
+            match val.1.1 {
+                Vote::Aye => yes_balance = yes_balance.saturating_add(weight),
+                Vote::Nay => no_balance = no_balance.saturating_add(weight),
+                Vote::Abstain => nu_balance = nu_balance.saturating_add(weight),
             }
         });
-        (yes_balance,no_balance)
+        (yes_balance,no_balance,nu_balance)
     }
     pub fn is_expired(&self, current: BlockNumber) -> bool {
         current > self.end_dt
     }
 
     pub fn is_passed(&self, total_balance: Balance) -> bool {
-        let (yes_balance,no_balance) = self.summary();
-        let nu_balance = Zero::zero();
-        self.sub_param.is_passed(yes_balance, no_balance, nu_balance, total_balance)
+        self.sub_param.is_passed_by(self.summary(), self.tokens(), total_balance)
     }
     pub fn creator(&self) -> AccountId {
         self.creator.clone()
@@ -125,59 +253,106 @@ impl<AssetId: Clone + Default> AssetInfo<AssetId> {
     }
 }
 
+/// The policy of admitting new members into the organization.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AdmissionPolicy {
+    /// every member in the organization can add new members
+    Open,
+    /// new members can only be added by the passed proposals
+    ProposalRequired,
+    /// a member sponsors the new member by a proposal, and it was added after the proposal passed
+    SponsorVote,
+}
+
+impl Default for AdmissionPolicy {
+    fn default() -> Self {
+        AdmissionPolicy::Open
+    }
+}
+
 /// This structure is used to encode metadata about an organization.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct OrgInfo<AccountId, Balance,AssetId>
+pub struct OrgInfo<AccountId,Balance,AssetId>
 where
-    AccountId: Ord + Clone,
     Balance: Parameter + Member + PartialOrd + AtLeast32BitUnsigned,
     AssetId: Clone + Default,
 {
-    /// Accounts set of the organization
-    pub members: Vec<AccountId>,
     /// Parameters of the organization, set on organization creating
     pub param:  OrgRuleParam<Balance>,
+    /// The decision model of the organization, set on organization creating
+    pub rule: RuleKind,
+    /// The policy of admitting new members, set on organization creating
+    pub admission: AdmissionPolicy,
     /// The token for the organization
     pub asset: AssetInfo<AssetId>,
+    /// The voting mode of the proposals, set on organization creating
+    pub mode: VotingMode,
+    /// The account(or the account of a sub-committee organization) can veto the passed
+    /// proposals during the enactment delay, it can only be changed by a proposal
+    pub guardian: Option<AccountId>,
 }
 
 impl<
-    AccountId: Ord + Clone,
+    AccountId,
     Balance: Parameter + Member + PartialOrd + AtLeast32BitUnsigned,
     AssetId: Clone + Default,
-> OrgInfo<AccountId, Balance,AssetId> {
+> OrgInfo<AccountId,Balance,AssetId> {
     pub fn new() -> Self {
         Self{
-            members: Vec::new(),
             param: OrgRuleParam::default(),
+            rule: RuleKind::default(),
+            admission: AdmissionPolicy::default(),
             asset: AssetInfo::default(),
+            mode: VotingMode::default(),
+            guardian: None,
         }
     }
-    /// Sort all the vectors inside the strutcture.
-    pub fn sort(&mut self) {
-        self.members.sort();
-    }
-    pub fn is_member(&self,mid: AccountId) -> bool {
-        self.members.iter().find(|&x| mid.eq(&x)).is_some()
-    }
     pub fn get_asset_id(&self) -> AssetId {
         self.asset.id()
     }
     pub fn set_asset_id(&mut self,id: AssetId) {
         self.asset.set_id(id)
     }
-    pub fn add_member(&mut self, member: AccountId) -> DispatchResult {
-        if !self.is_member(member.clone()) {
-            self.members.push(member);
-        }
-        Ok(())
-    }
-    pub fn counts(&self) -> u32 {
-        self.members.len() as u32
+}
+
+/// The membership of an account in the organization.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MemberRecord<BlockNumber> {
+    /// The block number when the account joined the organization
+    pub joined: BlockNumber,
+}
+
+/// An entry of the call whitelist of the organization, it allows all calls of the pallet(the
+/// index of the pallet in the runtime), or only the call variant `call` of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallFilter {
+    /// The index of the pallet in the runtime
+    pub pallet: u8,
+    /// The index of the call variant in the pallet, `None` for all calls of the pallet
+    pub call: Option<u8>,
+}
+
+impl CallFilter {
+    /// Whether the encoded call is allowed by the filter
+    pub fn allows(&self, encoded: &[u8]) -> bool {
+        encoded.first() == Some(&self.pallet) && self.call.map_or(true, |c| encoded.get(1) == Some(&c))
     }
 }
 
+/// The voting power delegated by a member to another member of the organization.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Delegation<AccountId, Balance> {
+    /// The member who votes on behalf of the delegator
+    pub target: AccountId,
+    /// The locked tokens of the delegator counted in the votes of the delegate
+    pub balance: Balance,
+}
+
 
 
 /// Represent a proposal as stored by the pallet.
@@ -192,6 +367,12 @@ where
     pub org: AccountId,
     pub call: Call,
     pub detail: ProposalDetail<AccountId, Balance, BlockNumber>,
+    /// The title of the proposal
+    pub title: Vec<u8>,
+    /// The hash or the CID of the off-chain document with the full rationale of the proposal
+    pub content: Vec<u8>,
+    /// The total dispatch weight of the calls, it is charged to the vote which executes the proposal
+    pub weight: Weight,
 }
 
 impl<
@@ -205,8 +386,22 @@ impl<
             org: id,
             call: calldata,
             detail: info,
+            title: Vec::new(),
+            content: Vec::new(),
+            weight: 0,
         }
     }
+    /// Set the title and the content reference of the proposal
+    pub fn with_metadata(mut self, title: Vec<u8>, content: Vec<u8>) -> Self {
+        self.title = title;
+        self.content = content;
+        self
+    }
+    /// Set the dispatch weight of the calls
+    pub fn with_weight(mut self, weight: Weight) -> Self {
+        self.weight = weight;
+        self
+    }
     pub fn creator(&self) -> AccountId {
         self.detail.creator()
     }
@@ -217,17 +412,8 @@ impl<
 
 impl<T: Trait> Module<T>  {
 
-    /// get proposal id by hash the content in the proposal
-    pub fn make_proposal_id(proposal: &ProposalOf<T>) -> ProposalIdOf<T> {
-        T::Hashing::hash_of(&[proposal.encode()])
-    }
     pub fn get_count_members(oid: T::AccountId) -> u32 {
-        match Self::get_orginfo_by_id(oid) {
-            Ok(org) => {
-                org.counts()
-            },
-            Err(_) => 0,
-        }
+        MemberCount::<T>::get(oid)
     }
 
     pub fn get_token_id_by_oid(oid: T::AccountId) -> Result<T::TokenId,DispatchResult> {
@@ -247,10 +433,21 @@ impl<T: Trait> Module<T>  {
         T::Finance::balance_of(id)
     }
 
-    pub fn is_passed(proposal: ProposalOf<T>) -> bool {
-        let total_balance = Self::get_total_token_by_oid(proposal.org);
-        match total_balance {
-            Ok(balance) => proposal.detail.is_passed(balance),
+    /// The maximum vote units of the organization in its voting mode, the balances of the members
+    /// are only read in the quadratic mode.
+    pub fn total_units_of(oid: T::AccountId, org: &OrgInfoOf<T>) -> BalanceOf<T> {
+        let aid = org.get_asset_id();
+        org.mode.total_units(T::TokenHandler::total(aid), Self::get_count_members(oid.clone()),
+            Members::<T>::iter_prefix(oid).map(|(who, _)| T::TokenHandler::total_balance_of(aid, &who)))
+    }
+    /// Check whether the proposal was passed at the block `height` by the rule of the organization
+    pub fn is_passed(proposal: ProposalOf<T>, height: T::BlockNumber) -> bool {
+        match Self::get_orginfo_by_id(proposal.org.clone()) {
+            Ok(org) => {
+                let total_balance = Self::total_units_of(proposal.org.clone(), &org);
+                let detail = Self::detail_with_delegations(&proposal);
+                T::RuleHandler::on_proposal_pass(height, (org.rule, total_balance), detail)
+            },
             Err(_) => false,
         }
     }
@@ -265,8 +462,27 @@ impl<T: Trait> Module<T>  {
         Self::reserve_to_vault(oid, who, value)
     }
 
+    /// Create the organization with the new token of `total` amount assigned to the `owner`,
+    /// the owner and the `members` are the initial members of the organization.
+    pub fn on_create_organization(owner: T::AccountId, total: T::Balance, info: OrgInfoOf<T>, members: Vec<T::AccountId>) -> DispatchResult {
+        // the new organization has no enactment delay, the guardian is set by a proposal after it
+        ensure!(info.param.is_valid(),Error::<T>::WrongRuleParam);
+        ensure!(info.guardian.is_none(),Error::<T>::GuardianNeedsDelay);
+        // the creator is a member too
+        ensure!(members.len() < T::MaxMembers::get() as usize,Error::<T>::TooManyMembers);
+        let asset_id = Self::create_new_token(owner.clone(),total);
+        let mut info = info;
+        let mut members = members;
+        members.insert(0, owner);
+        info.set_asset_id(asset_id);
+        Self::storage_new_organization(info, members)
+    }
+
     pub fn on_create_proposal(id:u32,who: T::AccountId,expire: T::BlockNumber,sub_param: OrgRuleParamOf<T>
-                              ,call: Box<<T as Trait>::Call>) ->DispatchResult {
+                              ,calls: Vec<<T as Trait>::Call>,title: Vec<u8>,content: Vec<u8>) ->DispatchResult {
+        ensure!(calls.len() <= T::MaxProposalCalls::get() as usize,Error::<T>::TooManyCalls);
+        ensure!(title.len() <= T::MaxTitleLength::get() as usize,Error::<T>::TitleTooLong);
+        ensure!(content.len() <= T::MaxContentLength::get() as usize,Error::<T>::ContentTooLong);
         let oid = Self::counter_2_orgid(id);
         let org = Self::get_orginfo_by_id(oid.clone())?;
         if !org.param.inherit_valid(sub_param.clone()) {
@@ -276,20 +492,24 @@ impl<T: Trait> Module<T>  {
         if !Self::is_member(oid.clone(),&who) {
             return Err(Error::<T>::NotMemberInOrg.into());
         }
+        Self::ensure_calls_allowed(oid.clone(),&calls)?;
+        let weight = calls.iter().fold(0 as Weight,|acc,call| acc.saturating_add(call.get_dispatch_info().weight));
+        ensure!(weight <= T::MaxProposalWeight::get(),Error::<T>::ProposalTooHeavy);
         let locked_balance = T::InherentStakeProposal::get();
         T::Finance::lock_balance(oid.clone(),who.clone(),locked_balance)?;
 
-        let proposal = Proposal {
-            org:    oid.clone(),
-            call: call.encode(),
-            detail: ProposalDetail::new(who,expire,sub_param),
-        };
-        Self::base_create_proposal(oid,proposal)
+        let proposal = Proposal::new(oid.clone(),calls.encode(),
+            ProposalDetail::new(who,expire,sub_param).with_mode(org.mode))
+            .with_metadata(title,content)
+            .with_weight(weight);
+        Self::base_create_proposal(id,proposal)?;
+        Ok(())
     }
 
-    pub fn on_vote_proposal(pid: ProposalIdOf<T>,who: T::AccountId,value: T::Balance, vote_for: bool, cur: T::BlockNumber) -> DispatchResult {
+    /// Vote the proposal, returns the weight of the calls which were executed by the vote.
+    pub fn on_vote_proposal(pid: ProposalId,who: T::AccountId,value: T::Balance, vote: Vote, conviction: Conviction, cur: T::BlockNumber) -> Result<Weight, DispatchError> {
         let proposal = Self::get_proposal_by_id(pid)?;
-        Self::vote_on_proposal(proposal.org, pid,who,value, vote_for,cur)
+        Self::vote_on_proposal(proposal.org, pid,who,value, vote,conviction,cur)
     }
     pub fn on_add_member_and_assign_token(owner: T::AccountId, who: T::AccountId, number: u32, value: T::Balance) -> dispatch::DispatchResult {
         let oid = Self::counter_2_orgid(number);
@@ -300,7 +520,10 @@ impl<T: Trait> Module<T>  {
                 ensure!(free >= value && value >= Zero::zero(),Error::<T>::TokenBalanceLow);
                 ensure!(Self::is_member(oid.clone(),&owner),Error::<T>::NotMemberInOrg);
                 ensure!(!Self::is_member(oid.clone(),&who),Error::<T>::MemberDuplicate);
+                let org = Self::get_orginfo_by_id(oid.clone())?;
+                ensure!(org.admission == AdmissionPolicy::Open,Error::<T>::AdmissionNotOpen);
                 Self::base_add_member_by_orgid(oid.clone(), who.clone())?;
+                Self::deposit_event(RawEvent::MemberAdded(oid.clone(),who.clone()));
                 if value > Zero::zero() {
                     T::TokenHandler::transfer(asset_id, &owner, &who, value)
                 } else {
@@ -311,4 +534,113 @@ impl<T: Trait> Module<T>  {
         }
     }
 
+    /// Add the member `who` into the organization by the organization itself, it works
+    /// for all the admission policies.
+    pub fn on_add_member(oid: T::AccountId, who: T::AccountId) -> dispatch::DispatchResult {
+        Self::get_orginfo_by_id(oid.clone())?;
+        ensure!(!Self::is_member(oid.clone(),&who),Error::<T>::MemberDuplicate);
+        Self::base_add_member_by_orgid(oid.clone(), who.clone())?;
+        Self::deposit_event(RawEvent::MemberAdded(oid,who));
+        Ok(())
+    }
+    /// The member `sponsor` creates a proposal to add the `who` into the organization,
+    /// it was only allowed by the `SponsorVote` admission policy.
+    pub fn on_sponsor_member(id: u32, sponsor: T::AccountId, who: T::AccountId, expire: T::BlockNumber) -> dispatch::DispatchResult {
+        let oid = Self::counter_2_orgid(id);
+        let org = Self::get_orginfo_by_id(oid.clone())?;
+        ensure!(org.admission == AdmissionPolicy::SponsorVote,Error::<T>::NotSponsorAdmission);
+        ensure!(!Self::is_member(oid.clone(),&who),Error::<T>::MemberDuplicate);
+
+        let call: <T as Trait>::Call = crate::Call::<T>::add_member(T::Lookup::unlookup(who)).into();
+        Self::on_create_proposal(id,sponsor,expire,org.param,vec![call],Vec::new(),Vec::new())
+    }
+    /// Check the calls are allowed by the call whitelist of the organization. The organization
+    /// without the whitelist allows all calls, and `set_call_whitelist` is always allowed so
+    /// the whitelist can be changed by a proposal. The calls wrapped in `dispatch_as_account`
+    /// and `dispatch_as_root` must be allowed as well as the wrappers.
+    pub fn ensure_calls_allowed(oid: T::AccountId, calls: &[<T as Trait>::Call]) -> dispatch::DispatchResult {
+        if let Some(whitelist) = CallWhitelist::<T>::get(&oid) {
+            let own: <T as Trait>::Call = crate::Call::<T>::set_call_whitelist(None).into();
+            let own = own.encode();
+            for call in calls.iter() {
+                ensure!(Self::is_call_allowed(&whitelist,&own,call),Error::<T>::CallNotAllowed);
+            }
+        }
+        Ok(())
+    }
+    fn is_call_allowed(whitelist: &[CallFilter], own: &[u8], call: &<T as Trait>::Call) -> bool {
+        let encoded = call.encode();
+        let allowed = encoded.get(..2) == own.get(..2) ||
+            whitelist.iter().any(|filter| filter.allows(&encoded));
+        allowed && match call.is_sub_type() {
+            Some(crate::Call::dispatch_as_account(inner)) | Some(crate::Call::dispatch_as_root(inner,_)) =>
+                Self::is_call_allowed(whitelist,own,inner),
+            _ => true,
+        }
+    }
+    /// Set or remove the call whitelist of the organization.
+    pub fn on_set_call_whitelist(oid: T::AccountId, whitelist: Option<Vec<CallFilter>>) -> dispatch::DispatchResult {
+        Self::get_orginfo_by_id(oid.clone())?;
+        CallWhitelist::<T>::mutate(&oid,|w| *w = whitelist.clone());
+        Self::deposit_event(RawEvent::CallWhitelistUpdated(oid,whitelist));
+        Ok(())
+    }
+    /// Set the blocks between passing and executing the proposals of the organization.
+    pub fn on_set_enactment_delay(oid: T::AccountId, delay: T::BlockNumber) -> dispatch::DispatchResult {
+        let org = Self::get_orginfo_by_id(oid.clone())?;
+        ensure!(!delay.is_zero() || org.guardian.is_none(),Error::<T>::GuardianNeedsDelay);
+        EnactmentDelay::<T>::insert(&oid,delay);
+        Self::deposit_event(RawEvent::EnactmentDelayUpdated(oid,delay));
+        Ok(())
+    }
+    /// Update the rule param of the organization, the open proposals keep the `sub_param`
+    /// they were created with.
+    pub fn on_update_org_param(oid: T::AccountId, param: OrgRuleParamOf<T>) -> dispatch::DispatchResult {
+        ensure!(param.is_valid(),Error::<T>::WrongRuleParam);
+        OrgInfos::<T>::try_mutate(oid.clone(),|infos| -> dispatch::DispatchResult {
+            let org = infos.as_mut().ok_or(Error::<T>::OrganizationNotFound)?;
+            org.param = param.clone();
+            Ok(())
+        })?;
+        Self::deposit_event(RawEvent::OrgParamUpdated(oid,param));
+        Ok(())
+    }
+    /// Set or remove the guardian of the organization.
+    pub fn on_set_guardian(oid: T::AccountId, guardian: Option<T::AccountId>) -> dispatch::DispatchResult {
+        ensure!(guardian.is_none() || !EnactmentDelay::<T>::get(&oid).is_zero(),Error::<T>::GuardianNeedsDelay);
+        OrgInfos::<T>::try_mutate(oid.clone(),|infos| -> dispatch::DispatchResult {
+            let org = infos.as_mut().ok_or(Error::<T>::OrganizationNotFound)?;
+            org.guardian = guardian.clone();
+            Ok(())
+        })?;
+        Self::deposit_event(RawEvent::GuardianUpdated(oid,guardian));
+        Ok(())
+    }
+    /// Remove the member `who` from the organization, the votes of the member on the open
+    /// proposals will be retracted and the proposals created by the member will be cancelled.
+    /// The tokens of the member are still kept by the member.
+    /// Remove the member from the organization, returns the weight of removing the delegations.
+    pub fn on_remove_member(oid: T::AccountId, who: T::AccountId) -> Result<Weight, DispatchError> {
+        let org = Self::get_orginfo_by_id(oid.clone())?;
+        ensure!(Self::is_member(oid.clone(),&who),Error::<T>::NotMemberInOrg);
+        ensure!(Self::get_count_members(oid.clone()) > 1,Error::<T>::LastMemberInOrg);
+        let aid = org.get_asset_id();
+
+        let proposals: Vec<(ProposalId,ProposalOf<T>)> = OrgProposals::<T>::get(oid.clone())
+            .into_iter()
+            .filter_map(|pid| Proposals::<T>::get(pid).map(|proposal| (pid,proposal)))
+            .collect();
+        for (pid,proposal) in proposals.iter() {
+            if proposal.creator() == who {
+                Self::base_release_proposal(oid.clone(),aid,*pid,proposal)?;
+                Self::deposit_event(RawEvent::ProposalCancelled(*pid));
+            } else if proposal.detail.votes.contains_key(&who) {
+                Self::base_retract_vote(aid,*pid,who.clone())?;
+            }
+        }
+        let undelegated = Self::base_undelegate_all(oid.clone(),aid,who.clone())?;
+        Self::base_remove_member_by_orgid(oid.clone(),who.clone())?;
+        Self::deposit_event(RawEvent::MemberRemoved(oid,who));
+        Ok(undelegated)
+    }
 }
@@ -0,0 +1,121 @@
+/*
+ * Copyright 2021 Idavoll Network
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use frame_support::traits::{Get, EnsureOrigin};
+use sp_runtime::{RuntimeDebug, traits::Saturating};
+use crate::{Module, Trait, BalanceOf, OrgCount, ProposalOf};
+use codec::{Decode, Encode};
+use sp_std::marker::PhantomData;
+
+/// The final tally of the passed proposal, the votes are counted in the voting mode of the
+/// organization without the conviction and the `total` is the total votes of the organization.
+#[derive(PartialEq, Eq, Clone, Copy, Default, RuntimeDebug, Encode, Decode)]
+pub struct Tally<Balance> {
+    pub ayes: Balance,
+    pub nays: Balance,
+    pub abstentions: Balance,
+    pub total: Balance,
+}
+
+/// The origin of the calls of the passed proposals.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode)]
+pub enum RawOrigin<AccountId, Balance> {
+    /// The organization passed the proposal with the tally.
+    Organization(AccountId, Tally<Balance>),
+}
+
+/// The origin of the idavoll pallet.
+pub type Origin<T> = RawOrigin<<T as frame_system::Trait>::AccountId, <T as Trait>::Balance>;
+
+impl<T: Trait> Module<T> {
+    /// The tally of the proposal with the delegated votes.
+    pub fn proposal_tally(proposal: &ProposalOf<T>) -> Tally<BalanceOf<T>> {
+        let total = Self::get_orginfo_by_id(proposal.org.clone())
+            .map(|org| Self::total_units_of(proposal.org.clone(), &org))
+            .unwrap_or_default();
+        let (ayes,nays,abstentions) = Self::detail_with_delegations(proposal).tokens();
+        Tally{ ayes, nays, abstentions, total }
+    }
+}
+
+/// Ensure the origin was the organization `Id` (the ordinal number of the organization),
+/// it succeeds with the tally of the proposal.
+pub struct EnsureOrg<T, Id>(PhantomData<(T, Id)>);
+impl<
+    O: Into<Result<Origin<T>, O>> + From<Origin<T>>,
+    T: Trait,
+    Id: Get<OrgCount>,
+> EnsureOrigin<O> for EnsureOrg<T, Id> {
+    type Success = Tally<BalanceOf<T>>;
+    fn try_origin(o: O) -> Result<Self::Success, O> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Organization(ref oid, tally) if *oid == Module::<T>::counter_2_orgid(Id::get()) => Ok(tally),
+            r => Err(O::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> O {
+        O::from(RawOrigin::Organization(Module::<T>::counter_2_orgid(Id::get()), Default::default()))
+    }
+}
+
+/// Ensure the origin was the organization `Id` and at least `N / D` of the total votes of the
+/// organization approved the proposal, it succeeds with the tally of the proposal.
+pub struct EnsureOrgThreshold<T, Id, N, D>(PhantomData<(T, Id, N, D)>);
+impl<
+    O: Into<Result<Origin<T>, O>> + From<Origin<T>>,
+    T: Trait,
+    Id: Get<OrgCount>,
+    N: Get<u32>,
+    D: Get<u32>,
+> EnsureOrigin<O> for EnsureOrgThreshold<T, Id, N, D> {
+    type Success = Tally<BalanceOf<T>>;
+    fn try_origin(o: O) -> Result<Self::Success, O> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Organization(ref oid, tally) if *oid == Module::<T>::counter_2_orgid(Id::get())
+                && tally.ayes.saturating_mul(D::get().into()) >= tally.total.saturating_mul(N::get().into()) => Ok(tally),
+            r => Err(O::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> O {
+        let tally = Tally{ ayes: N::get().into(), total: D::get().into(), ..Default::default() };
+        O::from(RawOrigin::Organization(Module::<T>::counter_2_orgid(Id::get()), tally))
+    }
+}
+
+/// Ensure the origin was the meta organization created at genesis, it succeeds with the tally
+/// of the proposal. It always fails if there was no organization created at genesis.
+pub struct EnsureMetaOrg<T>(PhantomData<T>);
+impl<
+    O: Into<Result<Origin<T>, O>> + From<Origin<T>>,
+    T: Trait,
+> EnsureOrigin<O> for EnsureMetaOrg<T> {
+    type Success = Tally<BalanceOf<T>>;
+    fn try_origin(o: O) -> Result<Self::Success, O> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Organization(ref oid, tally) if Module::<T>::meta_organization().as_ref() == Some(oid) => Ok(tally),
+            r => Err(O::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> O {
+        O::from(RawOrigin::Organization(Module::<T>::meta_organization().unwrap_or_default(), Default::default()))
+    }
+}
@@ -16,7 +16,7 @@
 
 
 
-use frame_support::{dispatch::{DispatchResult,Parameter} };
+use frame_support::{ensure, dispatch::{DispatchResult,Parameter} };
 // #[cfg(feature = "std")]
 // use std::collections::{HashMap as Map, hash_map::Entry as MapEntry};
 use sp_runtime::{
@@ -28,9 +28,11 @@ use sp_std::{cmp::PartialOrd, marker};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use codec::{Decode, Encode};
+use crate::{Trait, Error, BalanceOf, ProposalDetailOf};
 
 
-
+/// BaseRule is the rule engine of the organizations, it decides when the proposal was passed,
+/// expired and who can close it. The pallet takes the rule engine by `Trait::RuleHandler`.
 pub trait BaseRule {
     type AccountId;
     type BlockNumber;
@@ -42,9 +44,97 @@ pub trait BaseRule {
     fn on_can_close(creator: Self::AccountId,detail: Self::Params) -> DispatchResult;
 }
 
+/// The decision model of the organization, it was chosen when the organization created.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RuleKind {
+    /// passed by the percentage thresholds of the `sub_param` in the proposal
+    Threshold,
+    /// passed by more than half of the total tokens in 'Yes' votes
+    SimpleMajority,
+    /// passed by at least two-thirds of the total tokens in 'Yes' votes
+    SuperMajority,
+    /// passed by more than half of the total tokens take part in the vote,
+    /// and more 'Yes' votes than 'no' votes
+    QuorumMajority,
+    /// passed by all the tokens in 'Yes' votes
+    Unanimous,
+}
+
+impl Default for RuleKind {
+    fn default() -> Self {
+        RuleKind::Threshold
+    }
+}
+
+impl RuleKind {
+    /// Check the proposal by the conviction weighted `votes` and the unweighted `tokens` of
+    /// (aye, nay, abstain), the `total` can only be reached by the `tokens`.
+    pub fn is_passed<Balance>(&self, param: &OrgRuleParam<Balance>, votes: (Balance,Balance,Balance),
+                              tokens: (Balance,Balance,Balance), total: Balance) -> bool
+    where
+        Balance: Parameter + Member + PartialOrd + AtLeast32BitUnsigned,
+    {
+        match self {
+            RuleKind::Threshold => param.is_passed_by(votes, tokens, total),
+            RuleKind::SimpleMajority => tokens.0 > Perbill::from_percent(50) * total,
+            RuleKind::SuperMajority => !total.is_zero() &&
+                tokens.0.saturating_mul(3u32.into()) >= total.saturating_mul(2u32.into()),
+            RuleKind::QuorumMajority => {
+                let turnout = tokens.0.saturating_add(tokens.1).saturating_add(tokens.2);
+                turnout > Perbill::from_percent(50) * total && votes.0 > votes.1
+            },
+            RuleKind::Unanimous => !total.is_zero() && tokens.0 >= total,
+        }
+    }
+}
+
+/// DefaultRule is the rule engine of the organizations, the proposal was passed by the
+/// `RuleKind` of the organization and it can only be cancelled by the creator before it
+/// was finished.
+pub struct DefaultRule<T>(marker::PhantomData<T>);
+
+impl<T: Trait> BaseRule for DefaultRule<T> {
+    type AccountId = T::AccountId;
+    type BlockNumber = T::BlockNumber;
+    type Params = ProposalDetailOf<T>;
+    /// the rule kind and the total tokens of the organization
+    type Data = (RuleKind, BalanceOf<T>);
+
+    fn on_proposal_pass(height: Self::BlockNumber,content: Self::Data,detail: Self::Params) -> bool {
+        let (kind, total) = content;
+        !detail.is_expired(height) &&
+            kind.is_passed(&detail.sub_param, detail.summary(), detail.tokens(), total)
+    }
+    fn on_proposal_expired(height: Self::BlockNumber,detail: Self::Params) -> DispatchResult {
+        ensure!(detail.is_expired(height),Error::<T>::ProposalNotFinished);
+        Ok(())
+    }
+    fn on_can_close(creator: Self::AccountId,detail: Self::Params) -> DispatchResult {
+        ensure!(creator == detail.creator(),Error::<T>::NotProposalCreator);
+        Ok(())
+    }
+}
+
+/// The base of the thresholds in the `OrgRuleParam`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ApprovalMode {
+    /// the thresholds are the percentages of the total supply of the token
+    Supply,
+    /// the thresholds are the percentages of the votes taking part in the proposal
+    Participants,
+}
+
+impl Default for ApprovalMode {
+    fn default() -> Self {
+        ApprovalMode::Supply
+    }
+}
+
 /// OrgRuleParam was used to vote by decision, it passed by all 'TRUE',
 /// passed by more than 60% 'Yes' votes and less than 5% 'no' votes.
-/// 'pass' = 'yes > min_affirmative%' and 'no <= max_dissenting' and 'nul <= abstention'
+/// 'pass' = 'turnout >= quorum%' and 'yes > min_affirmative%' and 'no <= max_dissenting' and 'nul <= abstention'
 ///
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -59,6 +149,10 @@ where
     /// the abstention votes threshold in a organization,More than a certain
     /// number of abstentions on the proposal then it will gradually become invalid
     pub abstention: u32,
+    /// Minimum turnout(all the votes) threshold of the total supply, zero means no quorum
+    pub quorum: u32,
+    /// The base of the thresholds above except the quorum
+    pub approval: ApprovalMode,
     _phantom: marker::PhantomData<Balance>,
 }
 
@@ -68,6 +162,8 @@ impl<Balance: Parameter + Member + PartialOrd + AtLeast32BitUnsigned> OrgRulePar
             min_affirmative: 0_u32,
             max_dissenting: 0_u32,
             abstention: 0_u32,
+            quorum: 0_u32,
+            approval: ApprovalMode::default(),
             _phantom: marker::PhantomData,
         }
     }
@@ -76,20 +172,59 @@ impl<Balance: Parameter + Member + PartialOrd + AtLeast32BitUnsigned> OrgRulePar
             min_affirmative: a,
             max_dissenting: d,
             abstention: s,
+            quorum: 0_u32,
+            approval: ApprovalMode::default(),
             _phantom: marker::PhantomData,
         }
     }
+    /// Set the turnout quorum and the base of the thresholds
+    pub fn with_quorum(mut self, quorum: u32, approval: ApprovalMode) -> Self {
+        self.quorum = quorum;
+        self.approval = approval;
+        self
+    }
     pub fn is_passed(&self, yes_amount: Balance, no_amount: Balance, nu_amount: Balance, total: Balance) -> bool {
+        let turnout = yes_amount.clone().saturating_add(no_amount.clone()).saturating_add(nu_amount.clone());
+        if self.quorum != 0 && turnout < Perbill::from_percent(self.quorum) * total.clone() {
+            return false;
+        }
+        let base = match self.approval {
+            ApprovalMode::Supply => total,
+            ApprovalMode::Participants => turnout,
+        };
 
-        (self.min_affirmative == 0 || yes_amount > Perbill::from_percent(self.min_affirmative) * total.clone()) &&
-            (self.max_dissenting == 0 || !(no_amount > Perbill::from_percent(self.max_dissenting) * total.clone())) &&
-            (self.abstention == 0 || !(nu_amount > Perbill::from_percent(self.abstention) * total))
+        (self.min_affirmative == 0 || yes_amount > Perbill::from_percent(self.min_affirmative) * base.clone()) &&
+            (self.max_dissenting == 0 || !(no_amount > Perbill::from_percent(self.max_dissenting) * base.clone())) &&
+            (self.abstention == 0 || !(nu_amount > Perbill::from_percent(self.abstention) * base))
 
     }
+    /// Check the param by the conviction weighted `votes` and the unweighted `tokens`, the
+    /// turnout and the `Supply` thresholds are counted by the `tokens`, the `Participants`
+    /// thresholds are counted by the `votes` since they are the shares of the turnout.
+    pub fn is_passed_by(&self, votes: (Balance,Balance,Balance), tokens: (Balance,Balance,Balance), total: Balance) -> bool {
+        match self.approval {
+            ApprovalMode::Supply => self.is_passed(tokens.0, tokens.1, tokens.2, total),
+            ApprovalMode::Participants => {
+                let turnout = tokens.0.saturating_add(tokens.1).saturating_add(tokens.2);
+                !(self.quorum != 0 && turnout < Perbill::from_percent(self.quorum) * total.clone())
+                    && self.is_passed(votes.0, votes.1, votes.2, total)
+            },
+        }
+    }
+    /// Check the thresholds are the percentages in range 0 ~ 100
+    pub fn is_valid(&self) -> bool {
+        self.min_affirmative <= 100 && self.max_dissenting <= 100 && self.abstention <= 100
+            && self.quorum <= 100
+    }
+    /// The proposals can only use the stricter param with the same approval mode, the zero
+    /// `max_dissenting` and `abstention` are unlimited, so they can't replace the limits.
     pub fn inherit_valid(&self,subparam: OrgRuleParam<Balance>) -> bool {
+        let within = |limit: u32, sub: u32| limit == 0 || (sub != 0 && sub <= limit);
         subparam.min_affirmative >= self.min_affirmative
-            && subparam.max_dissenting <= self.max_dissenting
-            && subparam.abstention <= self.abstention
+            && within(self.max_dissenting, subparam.max_dissenting)
+            && within(self.abstention, subparam.abstention)
+            && subparam.quorum >= self.quorum
+            && subparam.approval == self.approval
     }
 }
 
@@ -15,9 +15,10 @@
  */
 
 
-use crate::{Error,mock::*};
-use frame_support::{assert_ok,assert_noop};
+use crate::{Error,Vote,Conviction,VotingMode,CallFilter,RuleKind,AdmissionPolicy,WeightInfo,Tally,EnsureOrg,EnsureOrgThreshold,EnsureMetaOrg,mock::*,rules::OrgRuleParam};
+use frame_support::{assert_ok,assert_noop,parameter_types,traits::{OnInitialize,EnsureOrigin},weights::GetDispatchInfo};
 use frame_system::RawOrigin;
+use codec::Encode;
 
 #[test]
 fn it_works_for_create_organization() {
@@ -97,10 +98,10 @@ fn only_members_can_create_proposals() {
 		// make the proposal with the proposal id
 		let call = make_transfer_proposal(10);
 		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
-		// let proposal_id = IdavollModule::make_proposal_id(&tmp_proposal.clone());
+		// let proposal_id = IdavollModule::next_proposal_id(c);
 
 		assert_noop!(IdavollModule::create_proposal(RawOrigin::Signed(A.clone()).into(),c,
-		5,tmp_proposal.detail.sub_param.clone(),call),Error::<Test>::NotMemberInOrg);
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]),Error::<Test>::NotMemberInOrg);
 	});
 }
 
@@ -120,16 +121,16 @@ fn it_works_for_first_dao() {
 		// make the proposal with the proposal id
 		let call = make_transfer_proposal(10);
 		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
-		let proposal_id = IdavollModule::make_proposal_id(&tmp_proposal.clone());
+		let proposal_id = IdavollModule::next_proposal_id(c);
 
 		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
-		5,tmp_proposal.detail.sub_param.clone(),call));
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
 		// get the proposal from the storage
 		assert_eq!(IdavollModule::get_proposal_by_id(proposal_id.clone()),Ok(tmp_proposal.clone()));
 
 		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0 as u64);
 		// vote for the proposal by the same proposal_id
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),80,true));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),80,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
 		// make sure the RECEIVER has the 'value' balance and the vault was reduce 'value' balance
 		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10 as u64);
 		assert_eq!(IdavollAsset::vault_balance_of(org_id.clone()),Ok(190 as u64));
@@ -152,10 +153,10 @@ fn it_works_for_dao_of_token_balance_change() {
 		// make the proposal with the proposal id
 		let call = make_transfer_proposal(10);
 		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
-		let proposal_id = IdavollModule::make_proposal_id(&tmp_proposal.clone());
+		let proposal_id = IdavollModule::next_proposal_id(c);
 
 		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
-		5,tmp_proposal.detail.sub_param.clone(),call));
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
 		// get the proposal from the storage
 		assert_eq!(IdavollModule::get_proposal_by_id(proposal_id.clone()),Ok(tmp_proposal.clone()));
 
@@ -168,21 +169,21 @@ fn it_works_for_dao_of_token_balance_change() {
 		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0 as u64);
 
 		// the owner voting on the proposal by 20 powers, it all locked 20 balance in the organization_id and proposal_id
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,true));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
 		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER.clone()),80);
 		assert_eq!(IdavollAsset::total_balance(asset_id,&OWNER.clone()),100);
-		// the owner voting on the proposal by 10 powers, it all locked 30 balance in the organization_id and proposal_id
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),10,true));
+		// the owner changes the vote to 30 powers, it all locked 30 balance in the organization_id and proposal_id
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),30,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
 		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER.clone()),70);
 		assert_eq!(IdavollAsset::total_balance(asset_id,&OWNER.clone()),100);
-		// the owner voting on the proposal by 30 powers, it all locked 60 balance in the organization_id and proposal_id
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),30,true));
+		// the owner changes the vote to 60 powers, it all locked 60 balance in the organization_id and proposal_id
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),60,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
 		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER.clone()),40);
 		assert_eq!(IdavollAsset::total_balance(asset_id,&OWNER.clone()),100);
-		// the owner voting on the proposal by 20 powers, it all locked 80 balance in the organization_id and proposal_id
+		// the owner changes the vote to 80 powers, it all locked 80 balance in the organization_id and proposal_id
 		// now the 'yes' vote was 80% of the all, it will pass the proposal, it will close the proposal and unlocked the user's
 		// balance. now the user(owner) has 100 balance ot the token
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,true));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),80,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
 		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER.clone()),100);
 		assert_eq!(IdavollAsset::total_balance(asset_id,&OWNER.clone()),100);
 
@@ -217,21 +218,21 @@ fn it_works_for_5_members_vote_pass() {
 		// make the proposal with the proposal id
 		let call = make_transfer_proposal(30);
 		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
-		let proposal_id = IdavollModule::make_proposal_id(&tmp_proposal.clone());
+		let proposal_id = IdavollModule::next_proposal_id(c);
 
 		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
-		5,tmp_proposal.detail.sub_param.clone(),call));
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
 		// get the proposal from the storage
 		assert_eq!(IdavollModule::get_proposal_by_id(proposal_id.clone()),Ok(tmp_proposal.clone()));
 
 		// vote for the proposal
 		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0 as u64);
 		// vote for the proposal by the same proposal_id
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,true));
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(1).into(),proposal_id.clone(),3,true));
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(2).into(),proposal_id.clone(),8,true));
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(3).into(),proposal_id.clone(),20,true));
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(4).into(),proposal_id.clone(),20,true));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(1).into(),proposal_id.clone(),3,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(2).into(),proposal_id.clone(),8,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(3).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(4).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
 
 		// the vote result, the proposal was passed，60% 'yes' votes was passed
 		// make sure the RECEIVER has the 'value' balance and the vault was reduce 'value' balance
@@ -265,21 +266,21 @@ fn it_works_for_5_members_vote_fail() {
 		// make the proposal with the proposal id
 		let call = make_transfer_proposal(30);
 		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
-		let proposal_id = IdavollModule::make_proposal_id(&tmp_proposal.clone());
+		let proposal_id = IdavollModule::next_proposal_id(c);
 
 		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
-		5,tmp_proposal.detail.sub_param.clone(),call));
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
 		// get the proposal from the storage
 		assert_eq!(IdavollModule::get_proposal_by_id(proposal_id.clone()),Ok(tmp_proposal.clone()));
 
 		// vote for the proposal
 		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0 as u64);
 		// vote for the proposal by the same proposal_id
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,true));
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(1).into(),proposal_id.clone(),3,true));
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(2).into(),proposal_id.clone(),8,false));
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(3).into(),proposal_id.clone(),20,true));
-		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(4).into(),proposal_id.clone(),20,true));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(1).into(),proposal_id.clone(),3,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(2).into(),proposal_id.clone(),8,Vote::Nay,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(3).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(4).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
 
 		// the vote result, the proposal was passed，60% 'yes' votes was passed
 		// make sure the RECEIVER has the 'value' balance and the vault was reduce 'value' balance
@@ -287,3 +288,1027 @@ fn it_works_for_5_members_vote_fail() {
 		assert_eq!(IdavollAsset::vault_balance_of(org_id.clone()),Ok(200));
 	});
 }
+
+#[test]
+fn it_works_for_abstain_votes() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let mut info = create_org();
+		// no more than 10% 'abstain' votes
+		info.param = OrgRuleParam::new(60,5,10);
+		assert_ok!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info.clone(),vec![]));
+		let org_id = IdavollModule::counter_2_orgid(c);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),1,c,15));
+
+		set_block_number(1);
+		let call = make_transfer_proposal(10);
+		let mut tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		tmp_proposal.detail.sub_param = info.param.clone();
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,info.param.clone(),call,vec![],vec![]));
+
+		// 15% 'abstain' votes, the proposal can't be passed by 80% 'yes' votes
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(1).into(),proposal_id.clone(),15,Vote::Abstain,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&1),0);
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),80,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::get_proposal_by_id(proposal_id.clone()).unwrap().detail.summary(),(80,0,15));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		assert_eq!(IdavollAsset::vault_balance_of(org_id.clone()),Ok(200));
+	});
+}
+
+#[test]
+fn it_works_for_closing_expired_proposals() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+
+		set_block_number(1);
+		// make 3 proposals expired at the block 6
+		let mut pids = vec![];
+		for i in 1..4 {
+			let call = make_transfer_proposal(i);
+			let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+			pids.push(IdavollModule::next_proposal_id(c));
+			assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+			5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		}
+		assert_eq!(IdavollModule::expiring_proposals(6),pids.clone());
+		assert_eq!(IdvBalances::free_balance(OWNER.clone()),300000 - 3);
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),pids[0].clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER.clone()),80);
+
+		// the proposals were not expired
+		IdavollModule::on_initialize(6);
+		assert_eq!(IdavollModule::count_of_proposals(),3);
+
+		// only 2 proposals will be closed in a block
+		set_block_number(7);
+		IdavollModule::on_initialize(7);
+		assert_eq!(IdavollModule::count_of_proposals(),1);
+		assert_eq!(IdavollModule::expiring_proposals(6),vec![]);
+		assert_eq!(IdavollModule::expiring_proposals(7),vec![pids[2].clone()]);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER.clone()),100);
+
+		set_block_number(8);
+		IdavollModule::on_initialize(8);
+		assert_eq!(IdavollModule::count_of_proposals(),0);
+		assert_eq!(IdavollModule::expiring_proposals(7),vec![]);
+		// the stake of the creator was released and nothing was transferred
+		assert_eq!(IdvBalances::free_balance(OWNER.clone()),300000);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		assert_eq!(IdavollAsset::vault_balance_of(org_id.clone()),Ok(200));
+	});
+}
+
+#[test]
+fn it_works_for_close_proposal() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+
+		set_block_number(1);
+		let call = make_transfer_proposal(10);
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+
+		// the proposal is in progress
+		assert_noop!(IdavollModule::close_proposal(RawOrigin::Signed(A).into(),proposal_id.clone(),MaxProposalWeight::get()),
+		Error::<Test>::ProposalNotFinished);
+
+		// anyone can close the expired proposal
+		set_block_number(7);
+		let info = IdavollModule::close_proposal(RawOrigin::Signed(A).into(),proposal_id.clone(),MaxProposalWeight::get()).unwrap();
+		assert_eq!(info.actual_weight,Some(<() as WeightInfo>::close_expired_proposals(1)));
+		assert_noop!(IdavollModule::get_proposal_by_id(proposal_id.clone()),Error::<Test>::ProposalNotFound);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER.clone()),100);
+		assert_eq!(IdvBalances::free_balance(OWNER.clone()),300000);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		assert_noop!(IdavollModule::close_proposal(RawOrigin::Signed(A).into(),proposal_id.clone(),MaxProposalWeight::get()),
+		Error::<Test>::ProposalNotFound);
+	});
+}
+
+#[test]
+fn it_works_for_cancel_proposal() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),1,c,10));
+
+		set_block_number(1);
+		let call = make_transfer_proposal(10);
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),20,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(1).into(),proposal_id.clone(),5,Vote::Nay,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(OWNER.clone()),300000 - 1);
+
+		// only the creator can cancel the proposal
+		assert_noop!(IdavollModule::cancel_proposal(RawOrigin::Signed(1).into(),proposal_id.clone()),
+		Error::<Test>::NotProposalCreator);
+		assert_ok!(IdavollModule::cancel_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone()));
+		assert_noop!(IdavollModule::get_proposal_by_id(proposal_id.clone()),Error::<Test>::ProposalNotFound);
+		assert_eq!(IdavollModule::expiring_proposals(6),vec![]);
+		// the tokens were unlocked and the stake was released
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER.clone()),90);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&1),10);
+		assert_eq!(IdvBalances::free_balance(OWNER.clone()),300000);
+		assert_eq!(IdavollAsset::vault_balance_of(org_id.clone()),Ok(200));
+	});
+}
+
+#[test]
+fn cancel_expired_proposal_should_not_work() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+
+		set_block_number(1);
+		let call = make_transfer_proposal(10);
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+
+		set_block_number(7);
+		assert_noop!(IdavollModule::cancel_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone()),
+		Error::<Test>::ProposalExpired);
+	});
+}
+
+#[test]
+fn it_works_for_simple_majority_organization() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let mut info = create_org();
+		info.rule = RuleKind::SimpleMajority;
+		assert_ok!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info.clone(),vec![]));
+		let org_id = IdavollModule::counter_2_orgid(c);
+		assert_eq!(IdavollModule::get_orginfo_by_id(org_id.clone()).unwrap().rule,RuleKind::SimpleMajority);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),1,c,40));
+
+		set_block_number(1);
+		let call = make_transfer_proposal(10);
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+
+		// 51% 'yes' votes was passed although the param needs 60%
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(1).into(),proposal_id.clone(),40,Vote::Nay,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),50,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),51,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+		assert_eq!(IdavollAsset::vault_balance_of(org_id.clone()),Ok(190));
+	});
+}
+
+#[test]
+fn it_works_for_leave_organization() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),A,c,15));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),B,c,15));
+		assert_eq!(IdavollModule::get_count_members(org_id),3);
+
+		set_block_number(1);
+		// the proposal created by A
+		let call = make_transfer_proposal(10);
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(A).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		assert_eq!(IdvBalances::free_balance(A),100000 - 200 - 1);
+		// the proposal created by the owner and voted by A and B
+		let call = make_transfer_proposal(20);
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(A).into(),proposal_id.clone(),10,Vote::Nay,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(B).into(),proposal_id.clone(),10,Vote::Nay,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::count_of_proposals(),2);
+
+		// A leave the organization
+		assert_ok!(IdavollModule::leave_organization(RawOrigin::Signed(A).into(),c));
+		assert_eq!(IdavollModule::is_member(org_id.clone(),&A),false);
+		assert_eq!(IdavollModule::get_count_members(org_id),2);
+		assert_eq!(IdavollModule::count_of_proposals(),1);
+		assert_eq!(IdvBalances::free_balance(A),100000 - 200);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),15);
+		let proposal = IdavollModule::get_proposal_by_id(proposal_id.clone()).unwrap();
+		assert_eq!(proposal.detail.summary(),(0,10,0));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&B),5);
+		assert_noop!(IdavollModule::leave_organization(RawOrigin::Signed(A).into(),c),Error::<Test>::NotMemberInOrg);
+	});
+}
+
+#[test]
+fn it_works_for_remove_member_by_proposal() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),B,c,15));
+
+		// only the organization can remove the member
+		assert_noop!(IdavollModule::remove_member(RawOrigin::Signed(OWNER.clone()).into(),B),
+		sp_runtime::DispatchError::BadOrigin);
+
+		set_block_number(1);
+		let call = make_transfer_proposal(20);
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let transfer_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(B).into(),transfer_id.clone(),5,Vote::Nay,Conviction::None,MaxProposalWeight::get()));
+
+		let call = vec![Call::IdavollModule(crate::Call::<Test>::remove_member(B))];
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+
+		assert_eq!(IdavollModule::is_member(org_id.clone(),&B),false);
+		assert_eq!(IdavollModule::get_count_members(org_id),1);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&B),15);
+		assert_eq!(IdavollModule::get_proposal_by_id(transfer_id.clone()).unwrap().detail.summary(),(0,0,0));
+
+		// the last member can't leave the organization
+		assert_noop!(IdavollModule::leave_organization(RawOrigin::Signed(OWNER.clone()).into(),c),
+		Error::<Test>::LastMemberInOrg);
+	});
+}
+
+#[test]
+fn it_works_for_proposal_required_admission() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let mut info = create_org();
+		info.admission = AdmissionPolicy::ProposalRequired;
+		assert_ok!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info.clone(),vec![]));
+		let org_id = IdavollModule::counter_2_orgid(c);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+
+		// the members can't add the new member directly
+		assert_noop!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),A,c,10),
+		Error::<Test>::AdmissionNotOpen);
+		assert_noop!(IdavollModule::add_member(RawOrigin::Signed(OWNER.clone()).into(),A),
+		sp_runtime::DispatchError::BadOrigin);
+		assert_noop!(IdavollModule::sponsor_member(RawOrigin::Signed(OWNER.clone()).into(),c,A,5),
+		Error::<Test>::NotSponsorAdmission);
+
+		set_block_number(1);
+		let call = vec![Call::IdavollModule(crate::Call::<Test>::add_member(A))];
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::is_member(org_id.clone(),&A),true);
+		assert_eq!(IdavollModule::get_count_members(org_id),2);
+	});
+}
+
+#[test]
+fn it_works_for_sponsor_vote_admission() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let mut info = create_org();
+		info.admission = AdmissionPolicy::SponsorVote;
+		assert_ok!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info.clone(),vec![]));
+		let org_id = IdavollModule::counter_2_orgid(c);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+
+		assert_noop!(IdavollModule::sponsor_member(RawOrigin::Signed(B).into(),c,A,5),
+		Error::<Test>::NotMemberInOrg);
+		assert_noop!(IdavollModule::sponsor_member(RawOrigin::Signed(OWNER.clone()).into(),c,OWNER.clone(),5),
+		Error::<Test>::MemberDuplicate);
+
+		set_block_number(1);
+		// the owner sponsors A by a proposal
+		let call = vec![Call::IdavollModule(crate::Call::<Test>::add_member(A))];
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::sponsor_member(RawOrigin::Signed(OWNER.clone()).into(),c,A,5));
+		assert_eq!(IdavollModule::get_proposal_by_id(proposal_id.clone()),Ok(tmp_proposal.clone()));
+		assert_eq!(IdavollModule::is_member(org_id.clone(),&A),false);
+
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::is_member(org_id.clone(),&A),true);
+	});
+}
+
+#[test]
+fn it_works_for_update_org_param() {
+	new_test_ext().execute_with(|| {
+		// the organization can't be created with the invalid param either
+		let mut info = create_org();
+		info.param = OrgRuleParam::new(101,5,0);
+		assert_noop!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info,vec![]),
+		Error::<Test>::WrongRuleParam);
+
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		let new_param = OrgRuleParam::new(80,5,0);
+
+		assert_noop!(IdavollModule::update_org_param(RawOrigin::Signed(OWNER.clone()).into(),new_param.clone()),
+		sp_runtime::DispatchError::BadOrigin);
+		assert_noop!(IdavollModule::update_org_param(org_origin(org_id.clone()),OrgRuleParam::new(101,5,0)),
+		Error::<Test>::WrongRuleParam);
+
+		set_block_number(1);
+		// the open proposal keep the old param
+		let call = make_transfer_proposal(10);
+		let old_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let old_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,old_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+
+		let call = vec![Call::IdavollModule(crate::Call::<Test>::update_org_param(new_param.clone()))];
+		let tmp_proposal = create_proposal_without_storage(org_id,5,call_to_vec(call.clone()));
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,tmp_proposal.detail.sub_param.clone(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id.clone(),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::get_orginfo_by_id(org_id.clone()).unwrap().param,new_param.clone());
+
+		// the new proposals must satisfy the new param
+		let call = make_transfer_proposal(20);
+		assert_noop!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),call,vec![],vec![]),Error::<Test>::WrongRuleParam);
+
+		assert_eq!(IdavollModule::get_proposal_by_id(old_id.clone()).unwrap().detail.sub_param,get_rule());
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),old_id.clone(),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+	});
+}
+
+#[test]
+fn it_works_for_identical_proposals() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+
+		set_block_number(1);
+		// the same monthly transfer can be proposed twice, they get the sequential index
+		for i in 0..2 {
+			let call = make_transfer_proposal(10);
+			assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+			5,get_rule(),call,vec![],vec![]));
+			assert!(IdavollModule::get_proposal_by_id((c,i)).is_ok());
+		}
+		assert_eq!(IdavollModule::next_proposal_id(c),(c,2));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,1),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_noop!(IdavollModule::get_proposal_by_id((c,1)),Error::<Test>::ProposalNotFound);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+		assert!(IdavollModule::get_proposal_by_id((c,0)).is_ok());
+		assert_eq!(org_id,IdavollModule::counter_2_orgid(c));
+	});
+}
+
+#[test]
+fn it_works_for_organization_proposals_index() {
+	new_test_ext().execute_with(|| {
+		let c1 = IdavollModule::counter_of();
+		let org1 = create_new_organization(OWNER.clone(),100);
+		let c2 = IdavollModule::counter_of();
+		let org2 = create_new_organization(OWNER.clone(),100);
+		assert_eq!(IdavollModule::count_of_organizations(),2);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c1,200));
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c2,200));
+
+		set_block_number(1);
+		for i in 0..2 {
+			assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c1,
+			5,get_rule(),make_transfer_proposal(10 + i),vec![],vec![]));
+		}
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c2,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		assert_eq!(IdavollModule::count_of_proposals(),3);
+		assert_eq!(IdavollModule::org_proposals(org1),vec![(c1,0),(c1,1)]);
+		assert_eq!(IdavollModule::org_proposals(org2),vec![(c2,0)]);
+
+		// the index is maintained when the proposals were cancelled or passed
+		assert_ok!(IdavollModule::cancel_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c1,0)));
+		assert_eq!(IdavollModule::org_proposals(org1),vec![(c1,1)]);
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c1,1),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::org_proposals(org1),vec![]);
+		assert_eq!(IdavollModule::count_of_proposals(),1);
+
+		// and when the proposals were closed after expired
+		set_block_number(7);
+		IdavollModule::on_initialize(7);
+		assert_eq!(IdavollModule::org_proposals(org2),vec![]);
+		assert_eq!(IdavollModule::count_of_proposals(),0);
+	});
+}
+
+#[test]
+fn it_works_for_changing_and_removing_votes() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),A,c,20));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),B,c,10));
+
+		set_block_number(1);
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		assert_noop!(IdavollModule::remove_vote(RawOrigin::Signed(A).into(),proposal_id,MaxProposalWeight::get()),Error::<Test>::VoteNotFound);
+
+		// the 'nay' vote was replaced by the 'aye' vote with the new value
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(A).into(),proposal_id,15,Vote::Nay,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),5);
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(A).into(),proposal_id,10,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),10);
+		let detail = IdavollModule::get_proposal_by_id(proposal_id).unwrap().detail;
+		assert_eq!(detail.summary(),(10,0,0));
+		assert_noop!(IdavollModule::vote_proposal(RawOrigin::Signed(A).into(),proposal_id,21,Vote::Aye,Conviction::None,MaxProposalWeight::get()),
+		Error::<Test>::TokenBalanceLow);
+
+		// the 'nay' vote of B blocks the proposal, it was passed after the vote was removed
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(B).into(),proposal_id,10,Vote::Nay,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id,70,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&B),0);
+		assert_ok!(IdavollModule::remove_vote(RawOrigin::Signed(B).into(),proposal_id,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),20);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&B),10);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER),70);
+	});
+}
+
+#[test]
+fn it_works_for_conviction_votes() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),A,c,20));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),B,c,10));
+
+		set_block_number(1);
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+
+		// 20 tokens locked with 'Locked3x' conviction take 60 votes
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(A).into(),proposal_id,20,Vote::Aye,Conviction::Locked3x,MaxProposalWeight::get()));
+		let detail = IdavollModule::get_proposal_by_id(proposal_id).unwrap().detail;
+		assert_eq!(detail.summary(),(60,0,0));
+		assert_eq!(detail.tokens(),(20,0,0));
+		// but the 60% of the total supply are counted by the tokens
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(B).into(),proposal_id,10,Vote::Aye,Conviction::Locked4x,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id,31,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+
+		// the tokens of A are kept locked for 2 locking periods after the proposal passed
+		assert_eq!(IdavollAsset::free_balance(asset_id,&B),0);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),0);
+		set_block_number(20);
+		IdavollAsset::on_initialize(20);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),0);
+		set_block_number(21);
+		IdavollAsset::on_initialize(21);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),20);
+	});
+}
+
+#[test]
+fn it_works_for_quadratic_voting() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let mut info = create_org();
+		info.mode = VotingMode::Quadratic;
+		assert_ok!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info,vec![]));
+		let org_id = IdavollModule::counter_2_orgid(c);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),A,c,36));
+		// the members without tokens don't raise the maximum votes
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),B,c,0));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),3,c,0));
+		let info = IdavollModule::get_orginfo_by_id(org_id.clone()).unwrap();
+		assert_eq!(IdavollModule::total_units_of(org_id.clone(),&info),14);
+
+		set_block_number(1);
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		// 36 tokens take 6 votes, the members take 14 votes at most, 60% of it can't be reached
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(A).into(),proposal_id,36,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		let detail = IdavollModule::get_proposal_by_id(proposal_id).unwrap().detail;
+		assert_eq!(detail.mode,VotingMode::Quadratic);
+		assert_eq!(detail.summary(),(6,0,0));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		// the owner takes 3 votes with 9 tokens rather than 64 votes
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id,9,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id).unwrap();
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER),64);
+	});
+}
+
+#[test]
+fn it_works_for_max_members() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		// the creator and the members can't be more than `MaxMembers`
+		let members: Vec<u128> = (1000..999 + MaxMembers::get() as u128).collect();
+		assert_noop!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,create_org(),
+		[members.clone(),vec![A]].concat()),Error::<Test>::TooManyMembers);
+		assert_ok!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,create_org(),members));
+		let org_id = IdavollModule::counter_2_orgid(c);
+		assert_eq!(IdavollModule::get_count_members(org_id.clone()),MaxMembers::get());
+		assert_noop!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),A,c,0),
+		Error::<Test>::TooManyMembers);
+		// the leaving member makes room for the new member
+		assert_ok!(IdavollModule::leave_organization(RawOrigin::Signed(1000).into(),c));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),A,c,0));
+	});
+}
+
+#[test]
+fn it_works_for_one_member_one_vote() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let mut info = create_org();
+		info.mode = VotingMode::Membership;
+		assert_ok!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info,vec![A,B,3]));
+		let org_id = IdavollModule::counter_2_orgid(c);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+
+		set_block_number(1);
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		// every member takes one vote no matter how many tokens it holds, no tokens are locked
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id,100,Vote::Aye,Conviction::Locked4x,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(A).into(),proposal_id,0,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER),100);
+		let detail = IdavollModule::get_proposal_by_id(proposal_id).unwrap().detail;
+		assert_eq!(detail.summary(),(2,0,0));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		// 3 of the 4 members is more than 60%
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(B).into(),proposal_id,0,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER),100);
+
+		// the delegation locks no tokens either
+		assert_ok!(IdavollModule::delegate(RawOrigin::Signed(OWNER.clone()).into(),c,A,None,100));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER),100);
+		assert_eq!(IdavollModule::delegations(org_id.clone(),(OWNER,None::<u8>)).unwrap().balance,0);
+		assert_ok!(IdavollModule::undelegate(RawOrigin::Signed(OWNER.clone()).into(),c,None));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER),100);
+	});
+}
+
+#[test]
+fn it_works_for_vote_delegation() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		let asset_id = IdavollModule::get_token_id_by_oid(org_id.clone()).unwrap();
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),A,c,30));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),B,c,20));
+		let category = make_transfer_proposal(10)[0].encode()[0];
+
+		assert_noop!(IdavollModule::delegate(RawOrigin::Signed(A).into(),c,A,None,30),Error::<Test>::DelegateToSelf);
+		assert_noop!(IdavollModule::delegate(RawOrigin::Signed(A).into(),c,RECEIVER,None,30),Error::<Test>::NotMemberInOrg);
+		assert_noop!(IdavollModule::undelegate(RawOrigin::Signed(A).into(),c,None),Error::<Test>::DelegationNotFound);
+		assert_ok!(IdavollModule::delegate(RawOrigin::Signed(A).into(),c,OWNER,None,30));
+		// the delegation of another call category doesn't apply to the proposal
+		assert_ok!(IdavollModule::delegate(RawOrigin::Signed(B).into(),c,OWNER,Some(category.wrapping_add(1)),20));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),0);
+		assert_eq!(IdavollModule::delegators(org_id.clone(),OWNER),vec![(A,None),(B,Some(category.wrapping_add(1)))]);
+		// the delegate takes 2 delegators at most, replacing the delegation is allowed
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),3,c,0));
+		assert_noop!(IdavollModule::delegate(RawOrigin::Signed(3).into(),c,OWNER,None,0),Error::<Test>::TooManyDelegators);
+		assert_ok!(IdavollModule::delegate(RawOrigin::Signed(A).into(),c,OWNER,None,30));
+
+		// the 31 votes of the owner take the 30 delegated tokens of A
+		set_block_number(1);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,0),31,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+		// the delegated tokens are kept locked after the proposal was closed
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),0);
+
+		// the direct vote of the delegator overrides the delegate
+		assert_ok!(IdavollModule::undelegate(RawOrigin::Signed(A).into(),c,None));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),30);
+		assert_ok!(IdavollModule::delegate(RawOrigin::Signed(A).into(),c,OWNER,Some(category),20));
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(A).into(),(c,1),10,Vote::Abstain,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,1),50,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+		// after the direct vote was removed, the delegated tokens are counted
+		assert_ok!(IdavollModule::remove_vote(RawOrigin::Signed(A).into(),(c,1),MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),20);
+
+		// the delegations of the leaving member and the delegations to it are removed
+		assert_ok!(IdavollModule::delegate(RawOrigin::Signed(3).into(),c,A,None,0));
+		let free = IdavollAsset::free_balance(asset_id,&A);
+		assert_ok!(IdavollModule::leave_organization(RawOrigin::Signed(A).into(),c));
+		assert_eq!(IdavollAsset::free_balance(asset_id,&A),free + 20);
+		assert_eq!(IdavollModule::delegations(org_id.clone(),(A,Some(category))),None);
+		assert_eq!(IdavollModule::delegations(org_id.clone(),(3,None::<u8>)),None);
+		assert_eq!(IdavollModule::delegators(org_id.clone(),A),vec![]);
+		assert_eq!(IdavollModule::delegators(org_id.clone(),OWNER),vec![(B,Some(category.wrapping_add(1)))]);
+		// the delegate takes the new delegator in place of the leaving member
+		assert_ok!(IdavollModule::delegate(RawOrigin::Signed(3).into(),c,OWNER,None,0));
+	});
+}
+
+#[test]
+fn it_works_for_enactment_delay_and_veto() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_noop!(IdavollModule::set_enactment_delay(RawOrigin::Signed(OWNER).into(),3),
+		sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(IdavollModule::set_enactment_delay(org_origin(org_id.clone()),3));
+		assert_eq!(IdavollModule::enactment_delay(org_id.clone()),3);
+
+		// the passed proposal is executed after the delay
+		set_block_number(1);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,0),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::get_proposal_by_id((c,0)).is_err(),true);
+		assert_eq!(IdavollModule::enactment_queue(4),vec![(c,0)]);
+		set_block_number(3);
+		IdavollModule::on_initialize(3);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		set_block_number(4);
+		IdavollModule::on_initialize(4);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+		assert_eq!(IdavollModule::queued_proposals((c,0)),None);
+
+		// the queued proposal is never executed after it was vetoed
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,1),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_noop!(IdavollModule::veto_proposal(RawOrigin::Signed(OWNER).into(),(c,1)),
+		Error::<Test>::NotGuardian);
+		assert_ok!(IdavollModule::veto_proposal(RawOrigin::Root.into(),(c,1)));
+		assert_noop!(IdavollModule::veto_proposal(RawOrigin::Root.into(),(c,1)),Error::<Test>::ProposalNotQueued);
+		assert_eq!(IdavollModule::enactment_queue(7),vec![]);
+		set_block_number(7);
+		IdavollModule::on_initialize(7);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+
+		// the proposals over the enactment weight of the block are moved to the next block
+		let mut calls = make_transfer_proposal(10);
+		calls.extend(make_transfer_proposal(10));
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),calls,vec![],vec![]));
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,2),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,3),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::enactment_queue(10),vec![(c,2),(c,3)]);
+		set_block_number(10);
+		IdavollModule::on_initialize(10);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),30);
+		assert_eq!(IdavollModule::enactment_queue(11),vec![(c,3)]);
+		set_block_number(11);
+		IdavollModule::on_initialize(11);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),40);
+	});
+}
+
+#[test]
+fn it_works_for_guardian_veto() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let mut info = create_org();
+		info.guardian = Some(B);
+		assert_noop!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info.clone(),vec![A]),
+		Error::<Test>::GuardianNeedsDelay);
+		info.guardian = None;
+		assert_ok!(IdavollModule::create_organization(RawOrigin::Signed(OWNER.clone()).into(),100,info,vec![A]));
+		let org_id = IdavollModule::counter_2_orgid(c);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		// the guardian needs a nonzero enactment delay
+		assert_noop!(IdavollModule::set_guardian(org_origin(org_id.clone()),Some(B)),Error::<Test>::GuardianNeedsDelay);
+		assert_ok!(IdavollModule::set_enactment_delay(org_origin(org_id.clone()),3));
+		assert_ok!(IdavollModule::set_guardian(org_origin(org_id.clone()),Some(B)));
+		assert_noop!(IdavollModule::set_enactment_delay(org_origin(org_id.clone()),0),Error::<Test>::GuardianNeedsDelay);
+
+		// the guardian vetoes the passed vault transfer
+		set_block_number(1);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,0),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_noop!(IdavollModule::veto_proposal(RawOrigin::Signed(A).into(),(c,0)),Error::<Test>::NotGuardian);
+		assert_ok!(IdavollModule::veto_proposal(RawOrigin::Signed(B).into(),(c,0)));
+		set_block_number(4);
+		IdavollModule::on_initialize(4);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+
+		// the guardian can only be removed by a proposal
+		assert_noop!(IdavollModule::set_guardian(RawOrigin::Signed(OWNER).into(),None),
+		sp_runtime::DispatchError::BadOrigin);
+		// the guardian still vetoes the transfer bundled with the enactment delay
+		let mut bundle = make_transfer_proposal(10);
+		bundle.push(Call::IdavollModule(crate::Call::<Test>::set_enactment_delay(3)));
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),bundle,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,1),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_ok!(IdavollModule::veto_proposal(RawOrigin::Signed(B).into(),(c,1)));
+
+		let remove_guardian = vec![Call::IdavollModule(crate::Call::<Test>::set_guardian(None))];
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),remove_guardian,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,2),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::get_orginfo_by_id(org_id.clone()).unwrap().guardian,Some(B));
+		// the guardian can't veto its removal
+		assert_noop!(IdavollModule::veto_proposal(RawOrigin::Signed(B).into(),(c,2)),Error::<Test>::GuardianVetoNotAllowed);
+		set_block_number(7);
+		IdavollModule::on_initialize(7);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+		assert_eq!(IdavollModule::get_orginfo_by_id(org_id.clone()).unwrap().guardian,None);
+	});
+}
+
+#[test]
+fn it_works_for_proposal_metadata() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		let title = b"pay the receiver".to_vec();
+		let content = [7u8; 32].to_vec();
+		assert_noop!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),[0u8; 17].to_vec(),content.clone()),Error::<Test>::TitleTooLong);
+		assert_noop!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),title.clone(),[0u8; 65].to_vec()),Error::<Test>::ContentTooLong);
+
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),title.clone(),content.clone()));
+		let proposal = IdavollModule::get_proposal_by_id((c,0)).unwrap();
+		assert_eq!(proposal.title,title);
+		assert_eq!(proposal.content,content);
+	});
+}
+
+#[test]
+fn it_works_for_multi_call_proposals() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		let transfer = |value| Call::IdavollModule(crate::Call::<Test>::vault_transfer(RECEIVER.clone(),value));
+		assert_noop!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),vec![transfer(1); 5],vec![],vec![]),Error::<Test>::TooManyCalls);
+
+		// all calls are executed in order
+		set_block_number(1);
+		let calls = vec![transfer(10),transfer(20),Call::IdavollModule(crate::Call::<Test>::add_member(A))];
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),calls,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,0),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),30);
+		assert_eq!(IdavollModule::is_member(org_id.clone(),&A),true);
+
+		// the changes of the calls are reverted when the last call failed
+		let calls = vec![transfer(10),transfer(1000)];
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),calls,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,1),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::get_proposal_by_id((c,1)).is_err(),true);
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),30);
+		assert_eq!(IdavollModule::get_local_balance(org_id),Ok(170));
+	});
+}
+
+#[test]
+fn it_works_for_call_whitelist() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		let transfer = make_transfer_proposal(10)[0].encode();
+		let whitelist = vec![CallFilter{ pallet: transfer[0], call: Some(transfer[1]) }];
+		assert_noop!(IdavollModule::set_call_whitelist(RawOrigin::Signed(OWNER).into(),Some(whitelist.clone())),
+		sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(IdavollModule::set_call_whitelist(org_origin(org_id.clone()),Some(whitelist.clone())));
+		assert_eq!(IdavollModule::call_whitelist(org_id.clone()),Some(whitelist.clone()));
+
+		// only the vault transfer and the whitelist update are allowed
+		let add_member = Call::IdavollModule(crate::Call::<Test>::add_member(A));
+		assert_noop!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),vec![make_transfer_proposal(10)[0].clone(),add_member.clone()],vec![],vec![]),Error::<Test>::CallNotAllowed);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),make_transfer_proposal(10),vec![],vec![]));
+
+		// the call wrapped in the whitelisted `dispatch_as_account` must be allowed too
+		let wrap = |call: Call| Call::IdavollModule(crate::Call::<Test>::dispatch_as_account(Box::new(call)));
+		let wrapper = wrap(add_member.clone()).encode();
+		let mut whitelist = whitelist;
+		whitelist.push(CallFilter{ pallet: wrapper[0], call: Some(wrapper[1]) });
+		assert_ok!(IdavollModule::set_call_whitelist(org_origin(org_id.clone()),Some(whitelist)));
+		assert_noop!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),vec![wrap(add_member.clone())],vec![],vec![]),Error::<Test>::CallNotAllowed);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),vec![wrap(make_transfer_proposal(10)[0].clone())],vec![],vec![]));
+
+		// the whitelist is removed by a proposal
+		set_block_number(1);
+		let remove_whitelist = vec![Call::IdavollModule(crate::Call::<Test>::set_call_whitelist(None))];
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),remove_whitelist,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,2),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::call_whitelist(org_id.clone()),None);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),vec![add_member],vec![],vec![]));
+	});
+}
+
+#[test]
+fn it_works_for_proposal_call_weight() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdavollModule::add_member_and_assign_token(RawOrigin::Signed(OWNER.clone()).into(),B,c,15));
+
+		// the calls are heavier than the MaxProposalWeight
+		let remove_member = Call::IdavollModule(crate::Call::<Test>::remove_member(B));
+		assert_noop!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),vec![remove_member.clone(),remove_member],vec![],vec![]),Error::<Test>::ProposalTooHeavy);
+
+		set_block_number(1);
+		let call = make_transfer_proposal(10);
+		let call_weight = call[0].get_dispatch_info().weight;
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),call,vec![],vec![]));
+		assert_eq!(IdavollModule::get_proposal_by_id(proposal_id).unwrap().weight,call_weight);
+
+		// the weight bound must cover the weight of the calls
+		assert_noop!(IdavollModule::vote_proposal(RawOrigin::Signed(B).into(),proposal_id,5,Vote::Aye,Conviction::None,call_weight - 1),
+		Error::<Test>::WrongProposalWeight);
+		let dispatch_info = crate::Call::<Test>::vote_proposal(proposal_id,5,Vote::Aye,Conviction::None,call_weight).get_dispatch_info();
+		assert_eq!(dispatch_info.weight,<() as WeightInfo>::vote_proposal() + call_weight);
+
+		// the vote doesn't execute the proposal, the weight of the calls is refunded
+		let info = IdavollModule::vote_proposal(RawOrigin::Signed(B).into(),proposal_id,5,Vote::Aye,Conviction::None,call_weight).unwrap();
+		assert_eq!(info.actual_weight,Some(<() as WeightInfo>::vote_proposal()));
+		// the finishing vote is charged for the weight of the calls
+		let info = IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id,61,Vote::Aye,Conviction::None,call_weight).unwrap();
+		assert_eq!(info.actual_weight,Some(<() as WeightInfo>::vote_proposal() + call_weight));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),10);
+	});
+}
+
+parameter_types! {
+	pub const FirstOrg: u32 = 0;
+	pub const SecondOrg: u32 = 1;
+	pub const Two: u32 = 2;
+	pub const Three: u32 = 3;
+}
+
+#[test]
+fn it_works_for_organization_origin() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+
+		// the organization account can't act as the organization
+		assert_noop!(IdavollModule::set_enactment_delay(RawOrigin::Signed(org_id.clone()).into(),3),
+		sp_runtime::DispatchError::BadOrigin);
+		assert!(EnsureOrg::<Test,FirstOrg>::try_origin(Origin::signed(org_id.clone())).is_err());
+
+		// the passed proposal carries the final tally
+		set_block_number(1);
+		let call = vec![Call::IdavollModule(crate::Call::<Test>::set_enactment_delay(3))];
+		let proposal_id = IdavollModule::next_proposal_id(c);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id,50,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		let proposal = IdavollModule::get_proposal_by_id(proposal_id).unwrap();
+		assert_eq!(IdavollModule::proposal_tally(&proposal),Tally{ ayes: 50, nays: 0, abstentions: 0, total: 100 });
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id,70,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdavollModule::enactment_delay(org_id.clone()),3);
+
+		// the other pallets require the decision of the organization at the approval level
+		let tally = Tally{ ayes: 70, nays: 0, abstentions: 0, total: 100 };
+		let origin = || -> Origin { crate::RawOrigin::Organization(org_id.clone(),tally).into() };
+		assert_eq!(EnsureOrg::<Test,FirstOrg>::try_origin(origin()).ok(),Some(tally));
+		assert!(EnsureOrg::<Test,SecondOrg>::try_origin(origin()).is_err());
+		assert_eq!(EnsureOrgThreshold::<Test,FirstOrg,Two,Three>::try_origin(origin()).ok(),Some(tally));
+		assert!(EnsureOrgThreshold::<Test,FirstOrg,Three,Three>::try_origin(origin()).is_err());
+		assert!(EnsureOrgThreshold::<Test,SecondOrg,Two,Three>::try_origin(origin()).is_err());
+	});
+}
+
+#[test]
+fn it_works_for_meta_organization() {
+	new_test_ext_with_organizations(vec![(OWNER.clone(),100,create_org(),vec![(B,30)])]).execute_with(|| {
+		// the meta organization was created at genesis
+		let org_id = IdavollModule::counter_2_orgid(0);
+		assert_eq!(IdavollModule::counter_of(),1);
+		assert_eq!(IdavollModule::get_count_members(org_id.clone()),2);
+		assert_eq!(IdavollModule::get_total_token_by_oid(org_id.clone()),Ok(100));
+		assert_eq!(IdavollModule::meta_organization(),Some(org_id.clone()));
+		let asset_id = IdavollModule::org_infos(org_id.clone()).unwrap().get_asset_id();
+		assert_eq!(IdavollAsset::free_balance(asset_id,&OWNER),70);
+		assert_eq!(IdavollAsset::free_balance(asset_id,&B),30);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),0,200));
+
+		let set_heap_pages = Box::new(Call::System(frame_system::Call::set_heap_pages(64)));
+		assert_noop!(IdavollModule::dispatch_as_root(RawOrigin::Signed(OWNER.clone()).into(),set_heap_pages.clone(),0),
+		sp_runtime::DispatchError::BadOrigin);
+
+		// the passed proposal of the meta organization dispatches the call with the Root origin
+		set_block_number(1);
+		let call = vec![Call::IdavollModule(crate::Call::<Test>::dispatch_as_root(set_heap_pages,0))];
+		let proposal_id = IdavollModule::next_proposal_id(0);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),0,
+		5,get_rule(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),proposal_id,61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(sp_io::storage::get(sp_core::storage::well_known_keys::HEAP_PAGES),Some(64u64.encode()));
+	});
+}
+
+#[test]
+fn it_works_for_no_meta_organization() {
+	new_test_ext().execute_with(|| {
+		// the organization number 0 created after genesis is not the meta organization
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_eq!(org_id,IdavollModule::counter_2_orgid(0));
+		assert_eq!(IdavollModule::meta_organization(),None);
+		assert!(EnsureMetaOrg::<Test>::try_origin(org_origin(org_id.clone())).is_err());
+
+		// the organization of the `MetaOrgNumber` becomes the meta organization on the upgrade
+		<IdavollModule as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(IdavollModule::meta_organization(),Some(org_id.clone()));
+		assert!(EnsureMetaOrg::<Test>::try_origin(org_origin(org_id)).is_ok());
+	});
+}
+
+#[test]
+fn it_works_for_dispatch_as_account() {
+	new_test_ext().execute_with(|| {
+		let c = IdavollModule::counter_of();
+		let org_id = create_new_organization(OWNER.clone(),100);
+		assert_ok!(IdavollModule::deposit_to_organization(RawOrigin::Signed(A).into(),c,200));
+		assert_ok!(IdvBalances::transfer(RawOrigin::Signed(A).into(),org_id.clone(),50));
+		let transfer = Box::new(Call::IdvBalances(pallet_balances::Call::transfer(RECEIVER.clone(),20)));
+		assert_noop!(IdavollModule::dispatch_as_account(RawOrigin::Signed(OWNER.clone()).into(),transfer.clone()),
+		sp_runtime::DispatchError::BadOrigin);
+
+		// the calls requiring a signed origin fail with the organization origin
+		set_block_number(1);
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),vec![*transfer.clone()],vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,0),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),0);
+
+		// they are dispatched with the signed origin of the organization id by `dispatch_as_account`
+		let call = vec![Call::IdavollModule(crate::Call::<Test>::dispatch_as_account(transfer))];
+		assert_ok!(IdavollModule::create_proposal(RawOrigin::Signed(OWNER.clone()).into(),c,
+		5,get_rule(),call,vec![],vec![]));
+		assert_ok!(IdavollModule::vote_proposal(RawOrigin::Signed(OWNER.clone()).into(),(c,1),61,Vote::Aye,Conviction::None,MaxProposalWeight::get()));
+		assert_eq!(IdvBalances::free_balance(RECEIVER.clone()),20);
+		assert_eq!(IdvBalances::free_balance(org_id),30);
+	});
+}
@@ -15,23 +15,32 @@
  */
 
 use frame_support::{
-    dispatch::{DispatchResult},
+    ensure,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo, IsSubType},
+    weights::Weight, StorageMap,
 };
-use crate::{ProposalIdOf, Error,Module, RawEvent, Trait,BalanceOf};
+use sp_runtime::traits::{One, Saturating, Zero};
+use crate::{ProposalId, Error,Module, Call, RawEvent, Trait,BalanceOf,Vote,Conviction,VotingMode,
+            ExpiringProposals,WeightInfo,ProposalOf,EnactmentDelay,QueuedProposals,EnactmentQueue};
+use crate::rules::BaseRule;
 use idavoll_asset::{token::BaseToken,finance::BaseFinance};
 use frame_support::traits::Get;
 
 
 impl<T: Trait> Module<T> {
     /// Vote the proposal.  lock the voter's token for voting the proposal, it will be auto unlocked
-    /// when the proposal closed
+    /// when the proposal closed. The vote replaces the previous vote of the voter, the tokens
+    /// locked by the previous vote will be unlocked. The vote weight is multiplied by the
+    /// `conviction`. In the `Membership` voting mode every member takes one vote without
+    /// locking any tokens. Returns the weight of the calls executed by the vote.
     pub fn vote_on_proposal(oid: T::AccountId,
-                            pid: ProposalIdOf<T>,
+                            pid: ProposalId,
                             voter: T::AccountId,
                             value: BalanceOf<T>,
-                            vote_for: bool,
+                            vote: Vote,
+                            conviction: Conviction,
                             height: T::BlockNumber,
-    ) -> DispatchResult {
+    ) -> Result<Weight, DispatchError> {
         if !Self::is_member(oid.clone(),&voter) {
             return Err(Error::<T>::NotMemberInOrg.into());
         }
@@ -42,40 +51,222 @@ impl<T: Trait> Module<T> {
             Self::try_close_proposal(oid.clone(),aid,pid,height)?;
             return Err(Error::<T>::ProposalExpired.into());
         }
-        // lock the voter's token
-        T::TokenHandler::lock(aid, &voter, value)?;
-        Self::base_vote_on_proposal(pid,voter,value,vote_for)?;
+        if oinfo.mode == VotingMode::Membership {
+            // every member takes one vote, the tokens and the conviction are ignored
+            Self::base_vote_on_proposal(pid,voter,Zero::zero(),vote,Conviction::None)?;
+        } else {
+            let previous = proposal.detail.votes.get(&voter).map(|val| val.0).unwrap_or_default();
+            let free = T::TokenHandler::free_balance_of(aid, &voter);
+            ensure!(free.saturating_add(previous) >= value,Error::<T>::TokenBalanceLow);
+            // replace the locked tokens of the previous vote
+            T::TokenHandler::unlock(aid, &voter, previous)?;
+            T::TokenHandler::lock(aid, &voter, value)?;
+            Self::base_vote_on_proposal(pid,voter,value,vote,conviction)?;
+        }
         // check the proposal can closed
         Self::try_close_proposal(oid.clone(),aid,pid,height)
     }
-    /// Try to close the proposal when the proposal was expire or passed.
-    /// It will auto unlock the voter's token
-    pub fn try_close_proposal(oid: T::AccountId, aid: T::TokenId, pid: ProposalIdOf<T>, height: T::BlockNumber) -> DispatchResult {
+    /// Remove the vote of the `voter` from the open proposal and unlock the tokens of the vote.
+    /// Returns the weight of the calls executed by the proposal passed without the vote.
+    pub fn on_remove_vote(pid: ProposalId, voter: T::AccountId, height: T::BlockNumber) -> Result<Weight, DispatchError> {
         let proposal = Self::get_proposal_by_id(pid)?;
-        let is_expired = proposal.detail.is_expired(height);
-        let is_passed = Self::is_passed(proposal.clone());
-        if is_passed && !is_expired {
-            Self::base_call_dispatch(pid,proposal.clone())?;
+        let oid = proposal.org.clone();
+        let aid = Self::get_orginfo_by_id(oid.clone())?.get_asset_id();
+        if proposal.detail.is_expired(height) {
+            Self::try_close_proposal(oid,aid,pid,height)?;
+            return Err(Error::<T>::ProposalExpired.into());
         }
+        ensure!(proposal.detail.votes.contains_key(&voter),Error::<T>::VoteNotFound);
+        Self::base_retract_vote(aid,pid,voter.clone())?;
+        Self::deposit_event(RawEvent::VoteRemoved(pid,voter));
+        // the proposal may pass without the dissenting votes
+        Self::try_close_proposal(oid,aid,pid,height)
+    }
+    /// Ensure the `weight_bound` given by the caller covers the weight of the calls of the
+    /// proposal `pid`, the caller is charged for it in advance.
+    pub fn ensure_weight_bound(pid: ProposalId, weight_bound: Weight) -> DispatchResult {
+        let proposal = Self::get_proposal_by_id(pid)?;
+        ensure!(proposal.weight <= weight_bound,Error::<T>::WrongProposalWeight);
+        Ok(())
+    }
+    /// The worst-case weight of checking whether a proposal was passed, all `MaxMembers` members
+    /// are counted and voted.
+    pub fn tally_weight() -> Weight {
+        Self::tally_weight_of(T::MaxMembers::get(), T::MaxMembers::get())
+    }
+    /// The weight of checking whether a proposal was passed, the total votes of the quadratic
+    /// voting are counted by reading the balances of the `members`, and the delegations of up to
+    /// `MaxDelegatorsPerDelegate` delegators are read for every one of the `voters`.
+    pub fn tally_weight_of(members: u32, voters: u32) -> Weight {
+        let per_voter = (T::MaxDelegatorsPerDelegate::get() as Weight).saturating_mul(3).saturating_add(1);
+        T::DbWeight::get().reads((voters as Weight).saturating_mul(per_voter).saturating_add(members as Weight))
+    }
+    /// The weight of checking whether the `proposal` was passed, one more voter is counted for
+    /// the vote which may be added to it.
+    pub fn proposal_tally_weight(proposal: &ProposalOf<T>) -> Weight {
+        let members = match proposal.detail.mode {
+            VotingMode::Quadratic => Self::get_count_members(proposal.org.clone()),
+            _ => 0,
+        };
+        Self::tally_weight_of(members, (proposal.detail.votes.len() as u32).saturating_add(1))
+    }
+    /// The worst-case weight of releasing a finished proposal, all `MaxMembers` members voted.
+    pub fn release_weight() -> Weight {
+        Self::release_weight_of(T::MaxMembers::get())
+    }
+    /// The weight of releasing a finished proposal with the `votes`, the tokens of every vote
+    /// are scheduled to be unlocked.
+    pub fn release_weight_of(votes: u32) -> Weight {
+        T::TokenHandler::unlock_at_weight().saturating_mul(votes as Weight)
+    }
+    /// Try to close the proposal when the proposal was expire or passed.
+    /// It will auto unlock the voter's token, the passed proposal is executed after the
+    /// enactment delay of the organization. Returns the weight of the executed calls.
+    pub fn try_close_proposal(oid: T::AccountId, aid: T::TokenId, pid: ProposalId, height: T::BlockNumber) -> Result<Weight, DispatchError> {
+        let proposal = Self::get_proposal_by_id(pid)?;
+        let is_expired = T::RuleHandler::on_proposal_expired(height.clone(), proposal.detail.clone()).is_ok();
+        let is_passed = Self::is_passed(proposal.clone(), height);
         if is_expired || is_passed {
-            Self::remove_proposal_by_id(pid);
-            proposal.detail.votes.iter().for_each(|val|{
-                match T::TokenHandler::unlock(aid, &val.0.clone(), val.1.0) {
-                    _ => {},
-                }
-            });
-            let proposal_creator = proposal.creator();
-            let locked_balance = T::InherentStakeProposal::get();
-            T::Finance::unlock_balance(oid,proposal_creator,locked_balance)?;
+            // release the proposal before the execution, so the call can't touch it again
+            Self::base_release_proposal(oid,aid,pid,&proposal)?;
             if is_expired {
                 Self::deposit_event(RawEvent::ProposalRefused(pid));
             }
             if is_passed {
                 Self::deposit_event(RawEvent::ProposalPassed(pid));
+                return Self::base_enact_proposal(pid,proposal,height);
             }
         }
+        Ok(0)
+    }
+    /// Execute the passed proposal, or queue it until the enactment delay of the organization ends.
+    /// Returns the weight of the executed calls, it's zero for the queued proposal.
+    fn base_enact_proposal(pid: ProposalId, proposal: ProposalOf<T>, height: T::BlockNumber) -> Result<Weight, DispatchError> {
+        let delay = EnactmentDelay::<T>::get(&proposal.org);
+        if delay.is_zero() {
+            return Self::base_call_dispatch(pid,proposal);
+        }
+        let when = height.saturating_add(delay);
+        QueuedProposals::<T>::insert(pid,(when,proposal));
+        EnactmentQueue::<T>::append(when,pid);
+        Self::deposit_event(RawEvent::ProposalQueued(pid,when));
+        Ok(0)
+    }
+    /// Execute the queued proposals at the block `height`, the proposals over the
+    /// `MaxEnactmentsPerBlock` or the `MaxEnactmentWeight` are moved to the next block.
+    /// The first proposal of the block is always executed, so the queue never gets stuck.
+    pub fn enact_queued_proposals(height: T::BlockNumber) -> Weight {
+        let pids = EnactmentQueue::<T>::take(height);
+        let max = T::MaxEnactmentsPerBlock::get() as usize;
+        let budget = T::MaxEnactmentWeight::get();
+        let (mut planned, mut weight): (Weight, Weight) = (0, 0);
+        let mut handled = 0;
+        for pid in pids.iter() {
+            if handled >= max {
+                break;
+            }
+            if let Some((_,proposal)) = QueuedProposals::<T>::get(pid) {
+                if handled > 0 && planned.saturating_add(proposal.weight) > budget {
+                    break;
+                }
+                planned = planned.saturating_add(proposal.weight);
+                QueuedProposals::<T>::remove(pid);
+                let used = Self::base_call_dispatch(*pid,proposal).unwrap_or_default();
+                weight = weight.saturating_add(used);
+            }
+            handled += 1;
+        }
+        if handled < pids.len() {
+            EnactmentQueue::<T>::mutate(height.saturating_add(One::one()), |v| v.extend_from_slice(&pids[handled..]));
+        }
+        T::WeightInfo::enact_queued_proposals(handled as u32).saturating_add(weight)
+    }
+    /// Remove the queued proposal before it was executed, the `guardian` must be the guardian
+    /// of the organization, or `None` for the `VetoOrigin`.
+    pub fn on_veto_proposal(pid: ProposalId, guardian: Option<T::AccountId>) -> DispatchResult {
+        let (when,proposal) = QueuedProposals::<T>::get(pid).ok_or(Error::<T>::ProposalNotQueued)?;
+        if guardian.is_some() {
+            let org = Self::get_orginfo_by_id(proposal.org.clone())?;
+            ensure!(org.guardian == guardian,Error::<T>::NotGuardian);
+            ensure!(!Self::is_governing_proposal(&proposal),Error::<T>::GuardianVetoNotAllowed);
+        }
+        QueuedProposals::<T>::remove(pid);
+        EnactmentQueue::<T>::mutate_exists(when,|maybe_pids| {
+            if let Some(pids) = maybe_pids {
+                pids.retain(|x| *x != pid);
+                if pids.is_empty() {
+                    *maybe_pids = None;
+                }
+            }
+        });
+        Self::deposit_event(RawEvent::ProposalVetoed(pid,guardian));
         Ok(())
     }
+    /// Whether all the calls of the proposal change the guardian or the enactment delay of the
+    /// organization, the guardian can't veto it, otherwise the guardian could never be removed.
+    /// The proposal bundling other calls with them can still be vetoed.
+    fn is_governing_proposal(proposal: &ProposalOf<T>) -> bool {
+        Self::decode_calls(&proposal.call).map(|calls| !calls.is_empty() && calls.iter().all(|call| {
+            match call.is_sub_type() {
+                Some(Call::set_guardian(..)) | Some(Call::set_enactment_delay(..)) => true,
+                _ => false,
+            }
+        })).unwrap_or(false)
+    }
+    /// Cancel the proposal before it was finished, the permission is checked by the rule
+    /// of the organization.
+    pub fn on_cancel_proposal(pid: ProposalId, who: T::AccountId, height: T::BlockNumber) -> DispatchResult {
+        let proposal = Self::get_proposal_by_id(pid)?;
+        ensure!(!proposal.detail.is_expired(height),Error::<T>::ProposalExpired);
+        T::RuleHandler::on_can_close(who,proposal.detail.clone())?;
+
+        let org = Self::get_orginfo_by_id(proposal.org.clone())?;
+        Self::base_release_proposal(proposal.org.clone(),org.get_asset_id(),pid,&proposal)?;
+        Self::deposit_event(RawEvent::ProposalCancelled(pid));
+        Ok(())
+    }
+    /// Close the proposal which was expired or passed, the weight of the expired proposal will
+    /// be refunded to the weight of closing an expired proposal, the passed proposal is charged
+    /// for the weight of the executed calls.
+    pub fn on_close_proposal(pid: ProposalId, height: T::BlockNumber) -> DispatchResultWithPostInfo {
+        let proposal = Self::get_proposal_by_id(pid)?;
+        let org = Self::get_orginfo_by_id(proposal.org.clone())?;
+        let is_expired = T::RuleHandler::on_proposal_expired(height.clone(), proposal.detail.clone()).is_ok();
+        let is_passed = Self::is_passed(proposal.clone(), height.clone());
+        ensure!(is_expired || is_passed, Error::<T>::ProposalNotFinished);
+
+        let tally = Self::proposal_tally_weight(&proposal)
+            .saturating_add(Self::release_weight_of(proposal.detail.votes.len() as u32));
+        let used = Self::try_close_proposal(proposal.org, org.get_asset_id(), pid, height)?;
+        if is_passed {
+            Ok(Some(T::WeightInfo::close_proposal().saturating_add(tally).saturating_add(used)).into())
+        } else {
+            Ok(Some(T::WeightInfo::close_expired_proposals(1).saturating_add(tally)).into())
+        }
+    }
+    /// Close the proposals which were expired at the block `height`, no more than `MaxExpiredPerBlock`
+    /// proposals will be closed in a block, the rest of them will be closed in the next block.
+    pub fn close_expired_proposals(height: T::BlockNumber) -> Weight {
+        let mut pids = ExpiringProposals::<T>::take(height.saturating_sub(One::one()));
+        let max = T::MaxExpiredPerBlock::get() as usize;
+        if pids.len() > max {
+            let rest = pids.split_off(max);
+            ExpiringProposals::<T>::mutate(height.clone(), |v| v.extend(rest));
+        }
+        let mut weight = T::WeightInfo::close_expired_proposals(pids.len() as u32);
+        for pid in pids.iter() {
+            if let Ok(proposal) = Self::get_proposal_by_id(*pid) {
+                if let Ok(org) = Self::get_orginfo_by_id(proposal.org.clone()) {
+                    weight = weight.saturating_add(Self::proposal_tally_weight(&proposal))
+                        .saturating_add(Self::release_weight_of(proposal.detail.votes.len() as u32));
+                    if let Ok(used) = Self::try_close_proposal(proposal.org, org.get_asset_id(), *pid, height.clone()) {
+                        weight = weight.saturating_add(used);
+                    }
+                }
+            }
+        }
+        weight
+    }
     /// Create new token for the new organization
     pub fn create_new_token(owner: T::AccountId,total: T::Balance) -> T::TokenId {
         T::TokenHandler::create(owner, total)
@@ -96,10 +96,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node"),
 	impl_name: create_runtime_str!("idavoll-node"),
 	authoring_version: 1,
-	spec_version: 0,
+	spec_version: 2,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 0,
+	transaction_version: 1,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -257,10 +257,6 @@ impl pallet_transaction_payment::Trait for Runtime {
 	type FeeMultiplierUpdate = ();
 }
 
-impl pallet_sudo::Trait for Runtime {
-	type Event = Event;
-	type Call = Call;
-}
 impl pallet_assets::Trait for Runtime {
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -269,6 +265,8 @@ impl pallet_assets::Trait for Runtime {
 }
 parameter_types! {
 	pub const IdvAssetModuleId: ModuleId = ModuleId(*b"py/asset");
+	pub const MaxUnlocksPerBlock: u32 = 256;
+	pub const MaxUnlockDelay: u32 = 16;
 	pub const IdavollModuleId: ModuleId = ModuleId(*b"py/idvol");
 }
 
@@ -278,20 +276,53 @@ impl idavoll_asset::Trait for Runtime {
 	type AssetId = u32;
 	type Currency = Balances;
 	type ModuleId = IdvAssetModuleId;
+	type MaxUnlocksPerBlock = MaxUnlocksPerBlock;
+	type MaxUnlockDelay = MaxUnlockDelay;
 }
 
 parameter_types! {
 	pub const InherentStakeProposal: Balance = 10;
+	pub const MaxExpiredPerBlock: u32 = 50;
+	pub const VoteLockingPeriod: BlockNumber = 7 * DAYS;
+	pub const MaxEnactmentsPerBlock: u32 = 50;
+	pub const MaxTitleLength: u32 = 128;
+	pub const MaxContentLength: u32 = 128;
+	pub const MaxProposalCalls: u32 = 16;
+	pub const MaxDelegatorsPerDelegate: u32 = 16;
+	pub const MaxMembers: u32 = 256;
+	pub MaxProposalWeight: Weight = Perbill::from_percent(40) * MaximumBlockWeight::get();
+	pub MaxEnactmentWeight: Weight = Perbill::from_percent(50) * MaximumBlockWeight::get();
+	/// The first organization of the chain governs the network after the upgrade.
+	pub const MetaOrgNumber: Option<u32> = Some(0);
 }
+
+/// The passed proposals of the meta organization can dispatch the calls with the Root origin.
+pub type EnsureRootOrg = pallet_idavoll::EnsureMetaOrg<Runtime>;
+
 impl pallet_idavoll::Trait for Runtime {
 	type Event = Event;
+	type Origin = Origin;
 	type Call = Call;
 	type Balance = Balance;
 	type TokenId = u32;
 	type ModuleId = IdavollModuleId;
 	type TokenHandler = IdvAsset;
 	type Finance = IdvAsset;
+	type RuleHandler = pallet_idavoll::DefaultRule<Runtime>;
 	type InherentStakeProposal = InherentStakeProposal;
+	type MaxExpiredPerBlock = MaxExpiredPerBlock;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type MaxEnactmentsPerBlock = MaxEnactmentsPerBlock;
+	type MaxEnactmentWeight = MaxEnactmentWeight;
+	type MaxTitleLength = MaxTitleLength;
+	type MaxContentLength = MaxContentLength;
+	type MaxProposalCalls = MaxProposalCalls;
+	type MaxDelegatorsPerDelegate = MaxDelegatorsPerDelegate;
+	type MaxMembers = MaxMembers;
+	type MaxProposalWeight = MaxProposalWeight;
+	type VetoOrigin = frame_system::EnsureRoot<AccountId>;
+	type MetaOrgNumber = MetaOrgNumber;
+	type RootOrigin = EnsureRootOrg;
 	type WeightInfo = ();
 }
 
@@ -309,13 +340,12 @@ construct_runtime!(
 		Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
-		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
 		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
 		// asset for vote and local asset(idv)
 		IdvAsset: idavoll_asset::{Module, Call, Storage, Event<T>},
 		// idavoll for DAO
-		Idavoll: pallet_idavoll::{Module, Call, Storage, Event<T>},
+		Idavoll: pallet_idavoll::{Module, Call, Storage, Config<T>, Event<T>, Origin<T>},
 	}
 );
 